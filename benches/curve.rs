@@ -0,0 +1,35 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust_ecc_curves::curve::EllipticCurve;
+use rust_ecc_curves::curve23::curve23;
+use rust_ecc_curves::curve61::curve61;
+use rust_ecc_curves::edwards25519::edwards25519;
+use rust_ecc_curves::ff::FiniteField;
+use rust_ecc_curves::nist::{p256, p384};
+use rust_ecc_curves::secp256k1::secp256k1;
+
+fn bench_point_mul<C: EllipticCurve>(c: &mut Criterion, name: &str, curve: &C) {
+    let mut group = c.benchmark_group(format!("point_mul_{}", name));
+
+    let p = curve.base_point();
+    let s = C::ScalarType::max();
+    group.bench_function("double_and_add", |bencher| {
+        bencher.iter(|| curve.point_mul(black_box(p), black_box(s)))
+    });
+    group.bench_function("montgomery_ladder", |bencher| {
+        bencher.iter(|| curve.point_mul_ladder(black_box(p), black_box(s)))
+    });
+
+    group.finish();
+}
+
+fn curve_benchmarks(c: &mut Criterion) {
+    bench_point_mul(c, "curve23", &curve23());
+    bench_point_mul(c, "curve61", &curve61());
+    bench_point_mul(c, "secp256k1", &secp256k1());
+    bench_point_mul(c, "p256", &p256());
+    bench_point_mul(c, "p384", &p384());
+    bench_point_mul(c, "edwards25519", &edwards25519());
+}
+
+criterion_group!(benches, curve_benchmarks);
+criterion_main!(benches);
@@ -0,0 +1,121 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rust_ecc_curves::bigint::Uint;
+use rust_ecc_curves::ff::FiniteField;
+use rust_ecc_curves::ff25519::FiniteField25519Point;
+use rust_ecc_curves::ff_big::{BigFp, PrimeModulus};
+use rust_ecc_curves::ff_mont::MontFp;
+use rust_ecc_curves::nist::{P256P, P384P};
+
+/// NIST P-256 prime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct P256;
+impl PrimeModulus<4> for P256 {
+    const P: Uint<4> =
+        Uint::from_be_hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+}
+
+/// NIST P-384 prime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct P384;
+impl PrimeModulus<6> for P384 {
+    const P: Uint<6> = Uint::from_be_hex(
+        "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffff0000000000000000ffffffff",
+    );
+}
+
+fn bench_mul<M: PrimeModulus<N>, const N: usize>(c: &mut Criterion, name: &str) {
+    let mut group = c.benchmark_group(format!("mul_{}", name));
+
+    let a = BigFp::<M, N>::max() - BigFp::from_u64(0x1234_5678);
+    let b = BigFp::<M, N>::max() - BigFp::from_u64(0x9abc_def0);
+    group.bench_function(BenchmarkId::new("plain", "schoolbook"), |bencher| {
+        bencher.iter(|| black_box(a) * black_box(b))
+    });
+    group.bench_function(BenchmarkId::new("plain", "karatsuba"), |bencher| {
+        bencher.iter(|| black_box(a).mul_karatsuba(&black_box(b)))
+    });
+
+    let ma = MontFp::<M, N>::new(a.value());
+    let mb = MontFp::<M, N>::new(b.value());
+    group.bench_function(BenchmarkId::new("montgomery", "schoolbook"), |bencher| {
+        bencher.iter(|| black_box(ma) * black_box(mb))
+    });
+
+    group.finish();
+}
+
+fn bench_inverse<M: PrimeModulus<N>, const N: usize>(c: &mut Criterion, name: &str) {
+    let mut group = c.benchmark_group(format!("inverse_{}", name));
+
+    let a = BigFp::<M, N>::max() - BigFp::from_u64(0x1234_5678);
+    group.bench_function("plain", |bencher| {
+        bencher.iter(|| black_box(a).mul_inverse())
+    });
+
+    let ma = MontFp::<M, N>::new(a.value());
+    group.bench_function("montgomery", |bencher| {
+        bencher.iter(|| black_box(ma).mul_inverse())
+    });
+
+    group.finish();
+}
+
+/// Plain multiplication with the Solinas reduction (see `nist.rs`) vs the long division
+fn bench_solinas<M: PrimeModulus<N>, S: PrimeModulus<N>, const N: usize>(
+    c: &mut Criterion,
+    name: &str,
+) {
+    let mut group = c.benchmark_group(format!("mul_{}", name));
+
+    let a = BigFp::<M, N>::max() - BigFp::from_u64(0x1234_5678);
+    let b = BigFp::<M, N>::max() - BigFp::from_u64(0x9abc_def0);
+    let sa = BigFp::<S, N>::new(a.value());
+    let sb = BigFp::<S, N>::new(b.value());
+    group.bench_function(BenchmarkId::new("solinas", "schoolbook"), |bencher| {
+        bencher.iter(|| black_box(sa) * black_box(sb))
+    });
+
+    group.finish();
+}
+
+/// 2^255 - 19
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct P25519;
+impl PrimeModulus<4> for P25519 {
+    const P: Uint<4> =
+        Uint::from_be_hex("7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed");
+}
+
+fn bench_25519(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mul_25519");
+
+    let a = MontFp::<P25519, 4>::max() - MontFp::from_u64(0x1234_5678);
+    let b = MontFp::<P25519, 4>::max() - MontFp::from_u64(0x9abc_def0);
+    group.bench_function("montgomery", |bencher| {
+        bencher.iter(|| black_box(a) * black_box(b))
+    });
+
+    let fa = FiniteField25519Point::new(a.value());
+    let fb = FiniteField25519Point::new(b.value());
+    group.bench_function("radix_2_51", |bencher| {
+        bencher.iter(|| black_box(fa) * black_box(fb))
+    });
+    group.bench_function("radix_2_51_square", |bencher| {
+        bencher.iter(|| black_box(fa).square())
+    });
+
+    group.finish();
+}
+
+fn field_benchmarks(c: &mut Criterion) {
+    bench_mul::<P256, 4>(c, "p256");
+    bench_mul::<P384, 6>(c, "p384");
+    bench_solinas::<P256, P256P, 4>(c, "p256");
+    bench_solinas::<P384, P384P, 6>(c, "p384");
+    bench_inverse::<P256, 4>(c, "p256");
+    bench_inverse::<P384, 6>(c, "p384");
+    bench_25519(c);
+}
+
+criterion_group!(benches, field_benchmarks);
+criterion_main!(benches);
@@ -0,0 +1,620 @@
+//! Fixed width (multi limbs) unsigned integers
+//!
+//! Limbs are u64 and stored in little endian order (limbs[0] is the least significant limb)
+
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Add with carry: a + b + carry, return (result, carry)
+#[inline(always)]
+pub const fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = (a as u128) + (b as u128) + (carry as u128);
+    (t as u64, (t >> 64) as u64)
+}
+
+/// Subtract with borrow: a - b - borrow, return (result, borrow) (borrow is 0 or 1)
+#[inline(always)]
+pub const fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub((b as u128) + (borrow as u128));
+    (t as u64, (t >> 127) as u64)
+}
+
+/// Multiply and accumulate: a + b * c + carry, return (result, carry)
+/// Note: cannot overflow as (2^64 - 1) + (2^64 - 1)^2 + (2^64 - 1) = 2^128 - 1
+#[inline(always)]
+pub const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = (a as u128) + (b as u128) * (c as u128) + (carry as u128);
+    (t as u64, (t >> 64) as u64)
+}
+
+/// A fixed width unsigned integer made of N u64 limbs
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Uint<const N: usize> {
+    limbs: [u64; N],
+}
+
+impl<const N: usize> Uint<N> {
+    pub const ZERO: Self = Self { limbs: [0; N] };
+    pub const ONE: Self = Self::from_u64(1);
+    pub const MAX: Self = Self {
+        limbs: [u64::MAX; N],
+    };
+    pub const BITS: u32 = 64 * N as u32;
+
+    pub const fn from_u64(value: u64) -> Self {
+        let mut limbs = [0; N];
+        limbs[0] = value;
+        Self { limbs }
+    }
+
+    pub const fn from_limbs(limbs: [u64; N]) -> Self {
+        Self { limbs }
+    }
+
+    pub const fn limbs(&self) -> &[u64; N] {
+        &self.limbs
+    }
+
+    /// Parse a big endian hex string (without 0x prefix), e.g. "ffffffff00000001"
+    /// Panic if the string is not valid hex or does not fit in N limbs
+    /// (can be used to define constants)
+    pub const fn from_be_hex(hex: &str) -> Self {
+        let bytes = hex.as_bytes();
+        assert!(bytes.len() <= 16 * N, "Hex string too long");
+
+        let mut limbs = [0; N];
+        let mut i = 0;
+        while i < bytes.len() {
+            // Read from the end (least significant digit first)
+            let c = bytes[bytes.len() - 1 - i];
+            let digit = match c {
+                b'0'..=b'9' => c - b'0',
+                b'a'..=b'f' => c - b'a' + 10,
+                b'A'..=b'F' => c - b'A' + 10,
+                _ => panic!("Invalid hex digit"),
+            };
+            limbs[i / 16] |= (digit as u64) << ((i % 16) * 4);
+            i += 1;
+        }
+
+        Self { limbs }
+    }
+
+    /// Parse big endian bytes (at most 8 * N bytes)
+    pub fn from_be_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() > 8 * N {
+            return None;
+        }
+
+        let mut limbs = [0; N];
+        for (i, b) in bytes.iter().rev().enumerate() {
+            limbs[i / 8] |= u64::from(*b) << ((i % 8) * 8);
+        }
+        Some(Self { limbs })
+    }
+
+    /// Parse little endian bytes (at most 8 * N bytes)
+    pub fn from_le_bytes(bytes: &[u8]) -> Option<Self> {
+        let be: Vec<u8> = bytes.iter().rev().copied().collect();
+        Self::from_be_bytes(&be)
+    }
+
+    /// Big endian encoding (8 * N bytes)
+    pub fn to_be_bytes(&self) -> Vec<u8> {
+        self.limbs
+            .iter()
+            .rev()
+            .flat_map(|l| l.to_be_bytes())
+            .collect()
+    }
+
+    /// Little endian encoding (8 * N bytes)
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        self.limbs.iter().flat_map(|l| l.to_le_bytes()).collect()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.iter().all(|l| *l == 0)
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.limbs[0] & 1 == 1
+    }
+
+    /// Value of the bit at index i (0 being the least significant bit)
+    pub fn bit(&self, i: u32) -> bool {
+        let i = i as usize;
+        (self.limbs[i / 64] >> (i % 64)) & 1 == 1
+    }
+
+    /// Number of bits needed to represent the value (0 for 0)
+    pub fn bits(&self) -> u32 {
+        for i in (0..N).rev() {
+            if self.limbs[i] != 0 {
+                return 64 * i as u32 + (64 - self.limbs[i].leading_zeros());
+            }
+        }
+        0
+    }
+
+    /// self + other, return (result, carry)
+    pub const fn overflowing_add(&self, other: &Self) -> (Self, bool) {
+        let mut limbs = [0; N];
+        let mut carry = 0;
+        let mut i = 0;
+        while i < N {
+            (limbs[i], carry) = adc(self.limbs[i], other.limbs[i], carry);
+            i += 1;
+        }
+        (Self { limbs }, carry == 1)
+    }
+
+    /// self - other, return (result, borrow)
+    pub const fn overflowing_sub(&self, other: &Self) -> (Self, bool) {
+        let mut limbs = [0; N];
+        let mut borrow = 0;
+        let mut i = 0;
+        while i < N {
+            (limbs[i], borrow) = sbb(self.limbs[i], other.limbs[i], borrow);
+            i += 1;
+        }
+        (Self { limbs }, borrow == 1)
+    }
+
+    /// self * 2, return (result, carry)
+    pub const fn shl1(&self) -> (Self, bool) {
+        let mut limbs = [0; N];
+        let mut carry = 0;
+        let mut i = 0;
+        while i < N {
+            limbs[i] = (self.limbs[i] << 1) | carry;
+            carry = self.limbs[i] >> 63;
+            i += 1;
+        }
+        (Self { limbs }, carry == 1)
+    }
+
+    /// Compute (self * 2) mod modulus (self must be lower than modulus)
+    /// Note: const fn so it can be used to compute constants (e.g. 2^k mod p)
+    pub const fn double_mod(&self, modulus: &Self) -> Self {
+        let (res, carry) = self.shl1();
+        // res - modulus, if there is no borrow: res >= modulus
+        let (reduced, borrow) = res.overflowing_sub(modulus);
+        if carry || !borrow {
+            reduced
+        } else {
+            res
+        }
+    }
+
+    /// self / 2
+    pub fn shr1(&self) -> Self {
+        let mut limbs = [0; N];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let high = if i + 1 < N {
+                self.limbs[i + 1] << 63
+            } else {
+                0
+            };
+            *limb = (self.limbs[i] >> 1) | high;
+        }
+        Self { limbs }
+    }
+
+    /// Schoolbook multiplication, return the 2N limbs result as (low, high)
+    pub fn mul_schoolbook(&self, other: &Self) -> (Self, Self) {
+        let mut res = [[0u64; N]; 2];
+
+        for i in 0..N {
+            let mut carry = 0;
+            for j in 0..N {
+                let k = i + j;
+                (res[k / N][k % N], carry) =
+                    mac(res[k / N][k % N], self.limbs[i], other.limbs[j], carry);
+            }
+            res[(i + N) / N][(i + N) % N] = carry;
+        }
+
+        (Self::from_limbs(res[0]), Self::from_limbs(res[1]))
+    }
+
+    /// Karatsuba multiplication, return the 2N limbs result as (low, high)
+    pub fn mul_karatsuba(&self, other: &Self) -> (Self, Self) {
+        let res = karatsuba(&self.limbs, &other.limbs);
+
+        let mut low = [0; N];
+        let mut high = [0; N];
+        low.copy_from_slice(&res[..N]);
+        high.copy_from_slice(&res[N..]);
+        (Self::from_limbs(low), Self::from_limbs(high))
+    }
+
+    /// Compute (high * 2^(64 * N) + low) mod modulus (modulus must not be 0)
+    ///
+    /// This is a plain (bit by bit) long division so it is slow but works for any modulus
+    pub fn rem_wide(low: &Self, high: &Self, modulus: &Self) -> Self {
+        let mut rem = Self::ZERO;
+
+        for value in [high, low] {
+            for i in (0..Self::BITS).rev() {
+                // rem = rem * 2 + bit
+                let (shifted, carry) = rem.shl1();
+                rem = shifted;
+                rem.limbs[0] |= u64::from(value.bit(i));
+
+                // rem < modulus before the shift so rem < 2 * modulus here
+                if carry || rem >= *modulus {
+                    rem = rem.overflowing_sub(modulus).0;
+                }
+            }
+        }
+
+        rem
+    }
+
+    /// Compute self mod modulus (modulus must not be 0)
+    pub fn rem(&self, modulus: &Self) -> Self {
+        Self::rem_wide(self, &Self::ZERO, modulus)
+    }
+}
+
+impl<const N: usize> Ord for Uint<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Compare from the most significant limb
+        self.limbs.iter().rev().cmp(other.limbs.iter().rev())
+    }
+}
+
+impl<const N: usize> PartialOrd for Uint<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> fmt::LowerHex for Uint<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for limb in self.limbs.iter().rev() {
+            write!(f, "{:016x}", limb)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> fmt::Debug for Uint<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{:x}", self)
+    }
+}
+
+impl<const N: usize> From<u64> for Uint<N> {
+    fn from(value: u64) -> Self {
+        Self::from_u64(value)
+    }
+}
+
+/// Schoolbook multiplication of 2 limb slices (any length)
+fn schoolbook(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut res = vec![0; a.len() + b.len()];
+
+    for (i, ai) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, bj) in b.iter().enumerate() {
+            (res[i + j], carry) = mac(res[i + j], *ai, *bj, carry);
+        }
+        res[i + b.len()] = carry;
+    }
+
+    res
+}
+
+/// a + b (a.len() >= b.len()), result has a.len() + 1 limbs
+fn add_slices(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut res = vec![0; a.len() + 1];
+    let mut carry = 0;
+    for i in 0..a.len() {
+        (res[i], carry) = adc(a[i], b.get(i).copied().unwrap_or(0), carry);
+    }
+    res[a.len()] = carry;
+    res
+}
+
+/// acc -= value (acc must be >= value)
+fn sub_assign_slice(acc: &mut [u64], value: &[u64]) {
+    let mut borrow = 0;
+    for (i, limb) in acc.iter_mut().enumerate() {
+        (*limb, borrow) = sbb(*limb, value.get(i).copied().unwrap_or(0), borrow);
+    }
+    debug_assert_eq!(borrow, 0);
+}
+
+/// acc += value * 2^(64 * offset) (the result must fit in acc)
+fn add_assign_slice_at(acc: &mut [u64], value: &[u64], offset: usize) {
+    let mut carry = 0;
+    for (i, limb) in acc.iter_mut().skip(offset).enumerate() {
+        (*limb, carry) = adc(*limb, value.get(i).copied().unwrap_or(0), carry);
+    }
+    // Limbs not added must be 0
+    debug_assert!(value.iter().skip(acc.len() - offset).all(|l| *l == 0));
+    debug_assert_eq!(carry, 0);
+}
+
+/// Karatsuba multiplication of 2 limb slices of the same length
+///
+/// From: https://en.wikipedia.org/wiki/Karatsuba_algorithm
+/// With B = 2^(64 * h), a = a1 * B + a0 and b = b1 * B + b0:
+///     a * b = z2 * B^2 + z1 * B + z0
+/// where z0 = a0 * b0, z2 = a1 * b1 and z1 = (a0 + a1) * (b0 + b1) - z0 - z2
+fn karatsuba(a: &[u64], b: &[u64]) -> Vec<u64> {
+    debug_assert_eq!(a.len(), b.len());
+
+    let n = a.len();
+    // (a0 + a1) has n - n / 2 + 1 limbs which is only smaller than n for n >= 4
+    if n < 4 {
+        return schoolbook(a, b);
+    }
+
+    let h = n / 2;
+    let (a0, a1) = a.split_at(h);
+    let (b0, b1) = b.split_at(h);
+
+    let z0 = karatsuba(a0, b0);
+    let z2 = karatsuba(a1, b1);
+    let mut z1 = karatsuba(&add_slices(a1, a0), &add_slices(b1, b0));
+    sub_assign_slice(&mut z1, &z0);
+    sub_assign_slice(&mut z1, &z2);
+
+    let mut res = vec![0; 2 * n];
+    add_assign_slice_at(&mut res, &z0, 0);
+    add_assign_slice_at(&mut res, &z1, h);
+    add_assign_slice_at(&mut res, &z2, 2 * h);
+    res
+}
+
+/// a - w for a limb slice, a must be >= w
+pub(crate) fn sub_word(a: &[u64], w: u64) -> Vec<u64> {
+    let mut res = a.to_vec();
+    sub_assign_slice(&mut res, &[w]);
+    res
+}
+
+/// a + w for a limb slice (with an extra limb for the carry)
+pub(crate) fn add_word(a: &[u64], w: u64) -> Vec<u64> {
+    add_slices(a, &[w])
+}
+
+/// a + b for limb slices of any length (with an extra limb for the carry)
+pub(crate) fn add(a: &[u64], b: &[u64]) -> Vec<u64> {
+    match a.len() >= b.len() {
+        true => add_slices(a, b),
+        false => add_slices(b, a),
+    }
+}
+
+/// a >> shift for a limb slice
+pub(crate) fn shr(a: &[u64], shift: u32) -> Vec<u64> {
+    let limb_shift = (shift / 64) as usize;
+    let bit_shift = shift % 64;
+
+    (0..a.len())
+        .map(|i| {
+            let low = a.get(i + limb_shift).copied().unwrap_or(0);
+            let high = a.get(i + limb_shift + 1).copied().unwrap_or(0);
+            if bit_shift == 0 {
+                low
+            } else {
+                (low >> bit_shift) | (high << (64 - bit_shift))
+            }
+        })
+        .collect()
+}
+
+/// Number of significant bits of a limb slice
+pub(crate) fn bits(a: &[u64]) -> u32 {
+    a.iter()
+        .rposition(|limb| *limb != 0)
+        .map_or(0, |i| 64 * i as u32 + 64 - a[i].leading_zeros())
+}
+
+/// Number of trailing zero bits of a limb slice
+pub(crate) fn trailing_zeros(a: &[u64]) -> u32 {
+    let mut res = 0;
+    for limb in a {
+        if *limb != 0 {
+            return res + limb.trailing_zeros();
+        }
+        res += 64;
+    }
+    res
+}
+
+/// Little endian u64 limbs view of an unsigned integer
+pub trait AsLimbs {
+    fn as_limbs(&self) -> &[u64];
+}
+
+impl AsLimbs for u64 {
+    fn as_limbs(&self) -> &[u64] {
+        std::slice::from_ref(self)
+    }
+}
+
+impl<const N: usize> AsLimbs for Uint<N> {
+    fn as_limbs(&self) -> &[u64] {
+        &self.limbs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type U256 = Uint<4>;
+
+    #[test]
+    fn test_from_be_hex() {
+        let a = U256::from_be_hex("0102030405060708090a0b0c0d0e0f10111213141516171819202122232425");
+        assert_eq!(
+            a.limbs(),
+            &[
+                0x1819202122232425,
+                0x1011121314151617,
+                0x0809_0a0b_0c0d_0e0f,
+                0x0001020304050607
+            ]
+        );
+        assert_eq!(U256::from_be_hex("ff"), U256::from_u64(255));
+        assert_eq!(U256::from_be_hex(""), U256::ZERO);
+        assert_eq!(
+            format!("{:?}", U256::from_u64(0xab)),
+            "0x00000000000000000000000000000000000000000000000000000000000000ab"
+        );
+    }
+
+    #[test]
+    fn test_bytes() {
+        let a =
+            U256::from_be_hex("0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20");
+        let be = a.to_be_bytes();
+        assert_eq!(be, (1..=32).collect::<Vec<u8>>());
+        assert_eq!(U256::from_be_bytes(&be), Some(a));
+
+        let le = a.to_le_bytes();
+        assert_eq!(le, (1..=32).rev().collect::<Vec<u8>>());
+        assert_eq!(U256::from_le_bytes(&le), Some(a));
+
+        assert_eq!(U256::from_be_bytes(&[1, 0]), Some(U256::from_u64(256)));
+        assert_eq!(U256::from_be_bytes(&[0; 33]), None);
+    }
+
+    #[test]
+    fn test_cmp_bits() {
+        let a = U256::from_limbs([u64::MAX, 0, 0, 1]);
+        let b = U256::from_limbs([0, 0, 0, 2]);
+        assert!(a < b);
+        assert!(U256::ZERO < U256::ONE);
+        assert_eq!(a.bits(), 193);
+        assert_eq!(U256::ZERO.bits(), 0);
+        assert_eq!(U256::MAX.bits(), 256);
+        assert!(a.bit(0) && a.bit(63) && !a.bit(64) && a.bit(192));
+    }
+
+    #[test]
+    fn test_add_sub() {
+        let (r, carry) = U256::MAX.overflowing_add(&U256::ONE);
+        assert_eq!((r, carry), (U256::ZERO, true));
+        let (r, borrow) = U256::ZERO.overflowing_sub(&U256::ONE);
+        assert_eq!((r, borrow), (U256::MAX, true));
+
+        // carry propagation accross limbs
+        let a = U256::from_limbs([u64::MAX, u64::MAX, 0, 0]);
+        let (r, carry) = a.overflowing_add(&U256::ONE);
+        assert_eq!((r, carry), (U256::from_limbs([0, 0, 1, 0]), false));
+        assert_eq!(r.overflowing_sub(&U256::ONE), (a, false));
+    }
+
+    #[test]
+    fn test_shifts() {
+        let a = U256::from_limbs([1 << 63, 0, 0, 1 << 63]);
+        assert_eq!(a.shl1(), (U256::from_limbs([0, 1, 0, 0]), true));
+        assert_eq!(a.shr1(), U256::from_limbs([1 << 62, 0, 0, 1 << 62]));
+
+        assert_eq!(shr(&[0, 0, 8], 131), vec![1, 0, 0]);
+        assert_eq!(shr(&[0b1100, 1], 2), vec![(1 << 62) | 0b11, 0]);
+        assert_eq!(trailing_zeros(&[0, 0b1000]), 67);
+        assert_eq!(sub_word(&[0, 1], 1), vec![u64::MAX, 0]);
+        assert_eq!(add_word(&[u64::MAX, u64::MAX], 1), vec![0, 0, 1]);
+        assert_eq!(add(&[1], &[u64::MAX, 2]), vec![0, 3, 0]);
+    }
+
+    #[test]
+    fn test_mul() {
+        // (2^256 - 1)^2 = 2^512 - 2^257 + 1
+        let (low, high) = U256::MAX.mul_schoolbook(&U256::MAX);
+        assert_eq!(low, U256::ONE);
+        assert_eq!(
+            high,
+            U256::from_limbs([u64::MAX - 1, u64::MAX, u64::MAX, u64::MAX])
+        );
+        assert_eq!(U256::MAX.mul_karatsuba(&U256::MAX), (low, high));
+
+        // Computed with python
+        let a =
+            U256::from_be_hex("c0ffee0123456789abcdef0123456789abcdef0123456789abcdef0123456789");
+        let b =
+            U256::from_be_hex("deadbeef00000000fffffffffffffffff0000000000000001111111111111111");
+        let (low, high) = a.mul_schoolbook(&b);
+        assert_eq!(
+            low,
+            U256::from_be_hex("f08a55f8111111106543210fedcba9876ffffffffffffffff48bdeffec94f919")
+        );
+        assert_eq!(
+            high,
+            U256::from_be_hex("a7e0ed4af4ef10ee8ca97478e04567776b677698ce1110fee99873338c26c28f")
+        );
+        assert_eq!(a.mul_karatsuba(&b), (low, high));
+    }
+
+    #[test]
+    fn test_karatsuba_sizes() {
+        // odd & even number of limbs
+        fn check<const N: usize>() {
+            let mut a = Uint::<N>::ZERO;
+            let mut b = Uint::<N>::MAX;
+            for i in 0..N as u64 {
+                a.limbs[i as usize] = 0x9e3779b97f4a7c15u64.wrapping_mul(i + 1);
+                b.limbs[i as usize] ^= 0xbf58476d1ce4e5b9u64.wrapping_mul(i + 3);
+            }
+            assert_eq!(a.mul_karatsuba(&b), a.mul_schoolbook(&b));
+            assert_eq!(b.mul_karatsuba(&b), b.mul_schoolbook(&b));
+            assert_eq!(
+                Uint::<N>::MAX.mul_karatsuba(&b),
+                Uint::<N>::MAX.mul_schoolbook(&b)
+            );
+        }
+        check::<1>();
+        check::<3>();
+        check::<4>();
+        check::<5>();
+        check::<6>();
+        check::<7>();
+        check::<9>();
+    }
+
+    #[test]
+    fn test_double_mod() {
+        let m = U256::from_u64(61);
+        let mut v = U256::ONE;
+        for _ in 0..256 {
+            v = v.double_mod(&m);
+        }
+        // 2^256 mod 61 = 22
+        assert_eq!(v, U256::from_u64(22));
+
+        let m = U256::from_limbs([u64::MAX - 188, u64::MAX, u64::MAX, u64::MAX]);
+        let v = U256::from_limbs([u64::MAX - 189, u64::MAX, u64::MAX, u64::MAX]);
+        assert_eq!(
+            v.double_mod(&m),
+            U256::from_limbs([u64::MAX - 190, u64::MAX, u64::MAX, u64::MAX])
+        );
+    }
+
+    #[test]
+    fn test_rem() {
+        let m = U256::from_u64(61);
+        assert_eq!(U256::from_u64(122).rem(&m), U256::ZERO);
+        assert_eq!(U256::from_u64(125).rem(&m), U256::from_u64(3));
+        // 2^256 mod 61 = 2^(256 mod 60) = 2^16 mod 61 = 22
+        assert_eq!(
+            U256::rem_wide(&U256::ZERO, &U256::ONE, &m),
+            U256::from_u64(22)
+        );
+
+        // Modulus with its highest bit set (check the carry handling)
+        let m = U256::from_limbs([u64::MAX - 188, u64::MAX, u64::MAX, u64::MAX]);
+        assert_eq!(U256::MAX.rem(&m), U256::from_u64(188));
+        assert_eq!(
+            U256::rem_wide(&U256::ZERO, &U256::ONE, &m),
+            U256::from_u64(189)
+        );
+    }
+}
@@ -0,0 +1,283 @@
+//! BIP-340 Schnorr signatures over secp256k1
+//!
+//! From: https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki
+//!
+//! Public keys are x-only: the 32 bytes x coordinate of P = d * G, the point with an even y is
+//! implied (d is negated if needed). Signatures are 64 bytes: x(R) || s with R of even y
+
+use crate::curve::{EllipticCurve, Point};
+use crate::ecdsa::be_bytes_to_limbs;
+use crate::ff::FiniteField;
+use crate::secp256k1::{secp256k1, Secp256k1, Secp256k1Field, Secp256k1Scalar};
+use crate::sha256::Sha256;
+
+/// SHA-256(SHA-256(tag) || SHA-256(tag) || x): a hash bound to its purpose (nonce, challenge..)
+pub fn tagged_hash(tag: &str, parts: &[&[u8]]) -> [u8; 32] {
+    let mut tag_hasher = Sha256::new();
+    tag_hasher.update(tag.as_bytes());
+    let tag_hash = tag_hasher.finalize();
+
+    let mut hasher = Sha256::new();
+    hasher.update(&tag_hash).update(&tag_hash);
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize()
+}
+
+/// Big endian bytes as a scalar (reduced mod n)
+fn scalar_from_bytes(bytes: &[u8]) -> Secp256k1Scalar {
+    Secp256k1Scalar::from_le_limbs(&be_bytes_to_limbs(bytes))
+}
+
+fn to_bytes32(x: &Secp256k1Field) -> [u8; 32] {
+    // Safe to unwrap: x < p is encoded with 32 bytes
+    x.to_be_bytes().try_into().unwrap()
+}
+
+/// The point P with the x coordinate x & an even y, None if x >= p or x^3 + 7 is not a square
+fn lift_x(c: &Secp256k1, x: &[u8]) -> Option<Point<Secp256k1Field>> {
+    let x = Secp256k1Field::from_be_bytes(x)?;
+    let (y1, y2) = c.eval_at(x)?;
+    Some(Point::new(x, if y1.is_odd() { y2 } else { y1 }))
+}
+
+/// The secret scalar d (negated if d * G has an odd y) & the x-only public key
+fn key_pair(c: &Secp256k1, secret: &[u8; 32]) -> Result<(Secp256k1Scalar, [u8; 32]), &'static str> {
+    let d = Secp256k1Scalar::from_be_bytes(secret)
+        .filter(|d| *d != Secp256k1Scalar::min())
+        .ok_or("Invalid secret key: must be in [1; n - 1]")?;
+
+    // Safe to unwrap: d != 0
+    let (x, y) = c.point_mul(c.base_point(), d).coordinates().unwrap();
+    let d = if y.is_odd() { -d } else { d };
+    Ok((d, to_bytes32(&x)))
+}
+
+/// e = tagged_hash("BIP0340/challenge", x(R) || x(P) || m) mod n
+fn challenge(r: &[u8], public: &[u8], message: &[u8]) -> Secp256k1Scalar {
+    scalar_from_bytes(&tagged_hash("BIP0340/challenge", &[r, public, message]))
+}
+
+/// x-only public key from a secret key (a big endian integer in [1; n - 1])
+pub fn public_key(secret: &[u8; 32]) -> Result<[u8; 32], &'static str> {
+    key_pair(&secp256k1(), secret).map(|(_, public)| public)
+}
+
+/// Sign a message: the nonce is derived from the secret key, the message & 32 bytes of
+/// auxiliary randomness (fresh random bytes are recommended, zeros are still safe)
+pub fn sign(
+    secret: &[u8; 32],
+    message: &[u8],
+    aux_rand: &[u8; 32],
+) -> Result<[u8; 64], &'static str> {
+    let c = secp256k1();
+    let (d, public) = key_pair(&c, secret)?;
+
+    // t = d xor tagged_hash("BIP0340/aux", a): the randomness is mixed in without replacing d
+    let aux = tagged_hash("BIP0340/aux", &[aux_rand]);
+    let t: Vec<u8> = d
+        .to_be_bytes()
+        .iter()
+        .zip(aux)
+        .map(|(d, a)| d ^ a)
+        .collect();
+
+    let k = scalar_from_bytes(&tagged_hash("BIP0340/nonce", &[&t, &public, message]));
+    if k == Secp256k1Scalar::min() {
+        return Err("Invalid nonce: k = 0");
+    }
+
+    // R = k * G (k negated if R has an odd y) & s = k + e * d
+    // Safe to unwrap: k != 0
+    let (rx, ry) = c.point_mul(c.base_point(), k).coordinates().unwrap();
+    let k = if ry.is_odd() { -k } else { k };
+    let r = to_bytes32(&rx);
+    let e = challenge(&r, &public, message);
+
+    let mut signature = [0; 64];
+    signature[..32].copy_from_slice(&r);
+    signature[32..].copy_from_slice(&(k + e * d).to_be_bytes());
+    Ok(signature)
+}
+
+/// Verify a signature: R = s * G - e * P must have an even y & x(R) = r
+pub fn verify(public: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> Result<(), &'static str> {
+    let c = secp256k1();
+    let p = lift_x(&c, public).ok_or("Invalid public key")?;
+    let r = Secp256k1Field::from_be_bytes(&signature[..32]).ok_or("Invalid signature: r >= p")?;
+    let s = Secp256k1Scalar::from_be_bytes(&signature[32..]).ok_or("Invalid signature: s >= n")?;
+
+    let e = challenge(&signature[..32], public, message);
+    let big_r = c.point_add(&c.point_mul(c.base_point(), s), &c.point_mul(p, -e));
+
+    match big_r.coordinates() {
+        Some((x, y)) if !y.is_odd() && x == r => Ok(()),
+        _ => Err("Invalid signature"),
+    }
+}
+
+/// Verify several (public key, message, signature) with a single equation:
+/// (a_1 * s_1 + ... + a_u * s_u) * G = a_1 * R_1 + a_1 * e_1 * P_1 + ... + a_u * e_u * P_u
+///
+/// Ok only if every signature is valid (with an overwhelming probability) but an error doesn't
+/// tell which one is invalid. The coefficients (a_1 = 1) must be unpredictable: they are
+/// derived from a hash of the whole batch (BIP-340 allows it instead of a CSPRNG)
+///
+/// From: https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki#batch-verification
+pub fn batch_verify(batch: &[(&[u8; 32], &[u8], &[u8; 64])]) -> Result<(), &'static str> {
+    let c = secp256k1();
+
+    // The message lengths are hashed too: every input is unambiguous
+    let mut hasher = Sha256::new();
+    for (public, message, signature) in batch {
+        hasher
+            .update(*public)
+            .update(&(message.len() as u64).to_be_bytes())
+            .update(message)
+            .update(*signature);
+    }
+    let seed = hasher.finalize();
+
+    // -(a_1 * s_1 + ... + a_u * s_u) is the scalar of G: the batch is valid if the sum is O
+    let mut s_sum = Secp256k1Scalar::min();
+    let mut terms = Vec::with_capacity(2 * batch.len() + 1);
+    for (i, (public, message, signature)) in batch.iter().enumerate() {
+        let p = lift_x(&c, *public).ok_or("Invalid public key")?;
+        let r = lift_x(&c, &signature[..32]).ok_or("Invalid signature: r is not on the curve")?;
+        let s =
+            Secp256k1Scalar::from_be_bytes(&signature[32..]).ok_or("Invalid signature: s >= n")?;
+        let e = challenge(&signature[..32], *public, message);
+
+        // "BIP0340/batch" is this crate's tag (not part of BIP-340)
+        let a = match i {
+            0 => Secp256k1Scalar::one(),
+            _ => scalar_from_bytes(&tagged_hash(
+                "BIP0340/batch",
+                &[&seed, &(i as u64).to_be_bytes()],
+            )),
+        };
+
+        s_sum = s_sum + a * s;
+        terms.push((r, a));
+        terms.push((p, a * e));
+    }
+    terms.push((c.base_point(), -s_sum));
+
+    match c.multi_mul(&terms).is_identity() {
+        true => Ok(()),
+        false => Err("Invalid signature"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn to_array<const N: usize>(bytes: &[u8]) -> [u8; N] {
+        bytes.try_into().unwrap()
+    }
+
+    /// (secret key, public key, aux_rand, message, signature, result, comment)
+    type Vector = (Vec<u8>, [u8; 32], Vec<u8>, Vec<u8>, [u8; 64], bool, String);
+
+    /// From: https://github.com/bitcoin/bips/blob/master/bip-0340/test-vectors.csv
+    /// (all of them: indexes 0 to 18, each one checked with a python port of the BIP-340
+    /// reference code)
+    fn test_vectors() -> Vec<Vector> {
+        include_str!("../test_vectors/bip340_test_vectors.csv")
+            .lines()
+            .skip(1)
+            .map(|line| {
+                let fields: Vec<&str> = line.splitn(8, ',').collect();
+                (
+                    from_hex(fields[1]),
+                    to_array(&from_hex(fields[2])),
+                    from_hex(fields[3]),
+                    from_hex(fields[4]),
+                    to_array(&from_hex(fields[5])),
+                    fields[6] == "TRUE",
+                    fields[7].to_string(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bip340_vectors() {
+        let vectors = test_vectors();
+        assert_eq!(vectors.len(), 19);
+
+        for (secret, public, aux_rand, message, signature, result, comment) in vectors {
+            if !secret.is_empty() {
+                let secret = to_array(&secret);
+                assert_eq!(public_key(&secret), Ok(public));
+                assert_eq!(sign(&secret, &message, &to_array(&aux_rand)), Ok(signature));
+            }
+            assert_eq!(
+                verify(&public, &message, &signature).is_ok(),
+                result,
+                "{comment}"
+            );
+            assert_eq!(
+                batch_verify(&[(&public, &message, &signature)]).is_ok(),
+                result,
+                "{comment}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_batch_verify() {
+        let vectors = test_vectors();
+        let (valid, invalid): (Vec<_>, Vec<_>) = vectors.iter().partition(|v| v.5);
+        let batch: Vec<_> = valid.iter().map(|v| (&v.1, &v.3[..], &v.4)).collect();
+        assert_eq!(batch_verify(&batch), Ok(()));
+        assert_eq!(batch_verify(&[]), Ok(()));
+
+        // A single invalid signature anywhere in the batch
+        for v in invalid {
+            for i in 0..=batch.len() {
+                let mut with_invalid = batch.clone();
+                with_invalid.insert(i, (&v.1, &v.3[..], &v.4));
+                assert!(batch_verify(&with_invalid).is_err(), "{}", v.6);
+            }
+        }
+
+        // Two invalid signatures which cancel out with a1 = a2 = 1: s1 + d & s2 - d
+        let secret = [7; 32];
+        let public = public_key(&secret).unwrap();
+        let mut first = sign(&secret, b"first", &[0; 32]).unwrap();
+        let mut second = sign(&secret, b"second", &[0; 32]).unwrap();
+        let one = Secp256k1Scalar::one();
+        let s1 = Secp256k1Scalar::from_be_bytes(&first[32..]).unwrap() + one;
+        let s2 = Secp256k1Scalar::from_be_bytes(&second[32..]).unwrap() - one;
+        first[32..].copy_from_slice(&s1.to_be_bytes());
+        second[32..].copy_from_slice(&s2.to_be_bytes());
+        assert!(verify(&public, b"first", &first).is_err());
+        assert!(verify(&public, b"second", &second).is_err());
+        assert!(batch_verify(&[
+            (&public, &b"first"[..], &first),
+            (&public, &b"second"[..], &second)
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_invalid_secret_key() {
+        let n: [u8; 32] = to_array(&from_hex(
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+        ));
+        for secret in [[0; 32], n] {
+            assert!(public_key(&secret).is_err());
+            assert!(sign(&secret, b"message", &[0; 32]).is_err());
+        }
+    }
+}
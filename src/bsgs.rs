@@ -0,0 +1,148 @@
+//! Baby-step giant-step: solve the elliptic curve discrete logarithm problem (ECDLP)
+//!
+//! From: https://en.wikipedia.org/wiki/Baby-step_giant-step
+//!
+//! Given P & Q = k * P, write k = i * m + j (0 <= j < m) then Q - i * (m * P) = j * P:
+//! * baby steps: store j * P for every j < m (memory: m points)
+//! * giant steps: compute Q - i * (m * P) for every i < g until a stored point is found (time:
+//!   at most g point additions)
+//!
+//! With m = g = sqrt(n) any k < n is found in O(sqrt(n)): instant on Curve61 (n = 73) but
+//! about 2^128 operations (& points to store) for a 256 bits curve
+
+use crate::bigint;
+use crate::curve::{EllipticCurve, Point};
+use crate::ff::FiniteField;
+use std::collections::HashMap;
+
+/// Point bytes used as the key of the baby steps table (nothing for the point at infinity)
+fn point_key<F: FiniteField>(p: &Point<F>) -> Vec<u8> {
+    match p.coordinates() {
+        None => vec![],
+        Some((x, y)) => [x.to_be_bytes(), y.to_be_bytes()].concat(),
+    }
+}
+
+/// Find the smallest k such that Q = k * P with k < baby_steps * giant_steps (None if there is
+/// none in that range)
+///
+/// Memory/time trade-off: the table stores `baby_steps` points & at most `giant_steps` point
+/// additions are needed to search it (plus `baby_steps` additions to build it)
+pub fn bsgs<C: EllipticCurve>(
+    c: &C,
+    p: &Point<C::ValueType>,
+    q: &Point<C::ValueType>,
+    baby_steps: u64,
+    giant_steps: u64,
+) -> Option<u64> {
+    // Baby steps: j * P -> j (the smallest j if the order of P is lower than m)
+    let mut table = HashMap::new();
+    let mut r = Point::Identity;
+    for j in 0..baby_steps {
+        table.entry(point_key(&r)).or_insert(j);
+        r = c.point_add(&r, p);
+    }
+
+    // Giant steps: Q - i * (m * P) with r = m * P
+    let stride = c.point_neg(&r);
+    let mut r = *q;
+    for i in 0..giant_steps {
+        if let Some(j) = table.get(&point_key(&r)) {
+            return Some(i * baby_steps + j);
+        }
+        r = c.point_add(&r, &stride);
+    }
+
+    None
+}
+
+/// Find k such that Q = k * P with P of order n (the scalar field modulus): m = g = ceil(sqrt(n))
+///
+/// Only usable for small orders (panics if n doesn't fit in a u64)
+pub fn discrete_log<C: EllipticCurve>(
+    c: &C,
+    p: &Point<C::ValueType>,
+    q: &Point<C::ValueType>,
+) -> Option<C::ScalarType> {
+    assert!(
+        bigint::bits(C::ScalarType::MODULUS) <= 64,
+        "Cannot solve a discrete logarithm with an order larger than 64 bits"
+    );
+
+    let n = C::ScalarType::MODULUS[0];
+    let m = match n.isqrt() {
+        s if s * s < n => s + 1,
+        s => s,
+    };
+    bsgs(c, p, q, m, m).map(C::ScalarType::from_u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve23::{curve23, Curve23Scalar};
+    use crate::curve61::{curve61, Curve61Scalar};
+    use crate::secp256k1::{secp256k1, Secp256k1Scalar};
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_break_key_exchange() {
+        // The public keys of Curve61 test_key_exchange reveal the private keys
+        let c61 = curve61();
+        let p = c61.base_point();
+        let A = c61.point_mul(p, Curve61Scalar::new(12));
+        let B = c61.point_mul(p, Curve61Scalar::new(7));
+
+        let kA = discrete_log(&c61, &p, &A).unwrap();
+        let kB = discrete_log(&c61, &p, &B).unwrap();
+        assert_eq!((kA, kB), (Curve61Scalar::new(12), Curve61Scalar::new(7)));
+        // Eve computes the shared secret kA * B = kB * A
+        assert_eq!(
+            c61.point_mul(B, kA),
+            c61.point_mul(A, Curve61Scalar::new(7))
+        );
+
+        // Same for Curve23
+        let c23 = curve23();
+        let p = c23.base_point();
+        let A = c23.point_mul(p, Curve23Scalar::new(12));
+        let B = c23.point_mul(p, Curve23Scalar::new(7));
+        assert_eq!(discrete_log(&c23, &p, &A), Some(Curve23Scalar::new(12)));
+        assert_eq!(discrete_log(&c23, &p, &B), Some(Curve23Scalar::new(7)));
+    }
+
+    #[test]
+    fn test_every_key() {
+        let c61 = curve61();
+        let p = c61.base_point();
+        for k in 0..Curve61Scalar::P {
+            let q = c61.point_mul(p, Curve61Scalar::new(k));
+            assert_eq!(discrete_log(&c61, &p, &q), Some(Curve61Scalar::new(k)));
+
+            // Any split of the 73 candidates: less memory, more time & vice versa
+            for (baby_steps, giant_steps) in [(1, 73), (3, 25), (73, 1)] {
+                assert_eq!(bsgs(&c61, &p, &q, baby_steps, giant_steps), Some(k));
+            }
+        }
+
+        // k out of the searched range [0; 2 * 5)
+        let q = c61.point_mul(p, Curve61Scalar::new(42));
+        assert_eq!(bsgs(&c61, &p, &q, 2, 5), None);
+
+        // Another base point P' = 20 * P: Q = 42 * P = (42 / 20) * P'
+        let p20 = c61.point_mul(p, Curve61Scalar::new(20));
+        let k = Curve61Scalar::new(42) * Curve61Scalar::new(20).mul_inverse().unwrap();
+        assert_eq!(discrete_log(&c61, &p20, &q), Some(k));
+    }
+
+    #[test]
+    fn test_small_key_secp256k1() {
+        // A 256 bits curve is only safe if the private key is random: k < 2^20 is found
+        // with 2^10 baby steps & at most 2^10 giant steps
+        let c = secp256k1();
+        let p = c.base_point();
+        let k = 1_000_003;
+        let q = c.point_mul(p, Secp256k1Scalar::from_u64(k));
+        assert_eq!(bsgs(&c, &p, &q, 1 << 10, 1 << 10), Some(k));
+    }
+}
@@ -0,0 +1,162 @@
+use crate::bigint::{self, AsLimbs};
+use crate::ff::FiniteField;
+
+use bitvec::prelude::*;
+
+/// A point of an elliptic curve: either an affine point (x, y) or the point at infinity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Point<F: FiniteField> {
+    /// The point at infinity (aka the identity element: P + O = P)
+    Identity,
+    Affine(F, F),
+}
+
+impl<F: FiniteField> Point<F> {
+    pub fn new(x: F, y: F) -> Self {
+        Self::Affine(x, y)
+    }
+
+    pub fn is_identity(&self) -> bool {
+        matches!(self, Self::Identity)
+    }
+
+    /// Affine coordinates (None for the point at infinity)
+    pub fn coordinates(&self) -> Option<(F, F)> {
+        match self {
+            Self::Identity => None,
+            Self::Affine(x, y) => Some((*x, *y)),
+        }
+    }
+}
+
+/// The affine points (x, y) of a curve found by evaluating it at every x of the field (by
+/// increasing x, then increasing y), see `EllipticCurve::points`
+///
+/// Panics if the field modulus doesn't fit in a u64
+pub(crate) fn affine_points<C: EllipticCurve + ?Sized>(
+    c: &C,
+) -> impl Iterator<Item = Point<C::ValueType>> + '_ {
+    assert!(
+        bigint::bits(C::ValueType::MODULUS) <= 64,
+        "Cannot enumerate the points of a curve over a field larger than 64 bits"
+    );
+
+    (0..C::ValueType::MODULUS[0]).flat_map(move |x| {
+        let x = C::ValueType::from_u64(x);
+        let ys = match c.eval_at(x) {
+            None => vec![],
+            Some((y1, y2)) if y1 == y2 => vec![y1],
+            Some((y1, y2)) => vec![y1, y2],
+        };
+        ys.into_iter().map(move |y| Point::new(x, y))
+    })
+}
+
+/// Montgomery ladder over any point representation T: r0 = O & r1 = P then, for every bit of
+/// the scalar (from the most significant bit of the scalar field modulus), r0 & r1 become
+/// (2 * r0, r0 + r1) or (r0 + r1, 2 * r1) so that r1 - r0 = P
+///
+/// From: https://en.wikipedia.org/wiki/Elliptic_curve_point_multiplication#Montgomery_ladder
+///
+/// `add` & `double` must be complete formulas (valid for any input) for the operations to
+/// be the same whatever the bits
+pub(crate) fn montgomery_ladder<S: FiniteField, T>(
+    s: S,
+    p: T,
+    identity: T,
+    add: impl Fn(&T, &T) -> T,
+    double: impl Fn(&T) -> T,
+) -> T {
+    let modulus_bits = S::MODULUS.view_bits::<Lsb0>();
+    let len = modulus_bits.last_one().map_or(0, |i| i + 1);
+
+    let num = s.value();
+    let bits = num.as_limbs().view_bits::<Lsb0>();
+
+    // Invariant: r1 - r0 = P
+    let mut r0 = identity;
+    let mut r1 = p;
+
+    for bit in bits[..len].iter().rev() {
+        if *bit {
+            r0 = add(&r0, &r1);
+            r1 = double(&r1);
+        } else {
+            r1 = add(&r0, &r1);
+            r0 = double(&r0);
+        }
+    }
+
+    r0
+}
+
+pub trait EllipticCurve {
+    /// The field of the point coordinates
+    type ValueType: FiniteField;
+    /// The field of the scalars: integers mod the order of the base point
+    type ScalarType: FiniteField;
+
+    fn base_point(&self) -> Point<Self::ValueType>;
+    fn eval_at(&self, x: Self::ValueType) -> Option<(Self::ValueType, Self::ValueType)>;
+    fn point_add(
+        &self,
+        p: &Point<Self::ValueType>,
+        q: &Point<Self::ValueType>,
+    ) -> Point<Self::ValueType>;
+
+    /// -P such that P + (-P) = O
+    fn point_neg(&self, p: &Point<Self::ValueType>) -> Point<Self::ValueType>;
+
+    /// 2 * P (curve implementations can provide a faster version than P + P)
+    fn point_double(&self, p: &Point<Self::ValueType>) -> Point<Self::ValueType> {
+        self.point_add(p, p)
+    }
+
+    fn point_mul(&self, p: Point<Self::ValueType>, s: Self::ScalarType) -> Point<Self::ValueType>;
+
+    /// Order of P: the smallest k > 0 such that k * P = O (found by enumeration: P, 2P, 3P...)
+    ///
+    /// Only usable for small orders (e.g. curves over small fields)
+    fn point_order(&self, p: &Point<Self::ValueType>) -> u64 {
+        let mut q = *p;
+        let mut order = 1;
+        while !q.is_identity() {
+            q = self.point_add(&q, p);
+            order += 1;
+        }
+        order
+    }
+
+    /// Every point of the curve: the identity first then the affine points (x, y) found by
+    /// evaluating the curve at every x of the field (by increasing x, then increasing y)
+    ///
+    /// Only usable over small fields (panics if the field modulus doesn't fit in a u64)
+    fn points(&self) -> impl Iterator<Item = Point<Self::ValueType>> + '_ {
+        std::iter::once(Point::Identity).chain(affine_points(self))
+    }
+
+    /// Number of points of the curve #E (including the identity), see `points`
+    fn group_order(&self) -> u64 {
+        self.points().count() as u64
+    }
+
+    /// Cofactor h = #E / n with n the order of the base point: scalars (e.g. private keys) are
+    /// only meaningful mod n & multiplying by h clears any small order component
+    ///
+    /// The default counts the points (see `group_order`), curves can provide the known value
+    fn cofactor(&self) -> u64 {
+        self.group_order() / self.point_order(&self.base_point())
+    }
+
+    /// Scalar multiplication using the Montgomery ladder: same result as `point_mul` but every
+    /// bit (up to the bit length of the scalar field modulus) costs one point addition and one
+    /// point doubling, whatever its value
+    ///
+    /// Implementations use complete projective formulas (see `montgomery_ladder`): no special
+    /// case for the identity, P = Q or P = -Q
+    fn point_mul_ladder(
+        &self,
+        p: Point<Self::ValueType>,
+        s: Self::ScalarType,
+    ) -> Point<Self::ValueType>;
+}
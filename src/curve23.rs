@@ -0,0 +1,134 @@
+use crate::curve::Point;
+use crate::ff::{FiniteField, Fp};
+use crate::ff23::FiniteField23Point;
+use crate::weierstrass::WeierstrassCurve;
+
+/// Curve23: y^2 = x^3 + x + 4 over 𝔽23
+pub type Curve23 = WeierstrassCurve<FiniteField23Point, Curve23Scalar>;
+
+/// Scalars of Curve23: integers mod 29 (the order of the base point)
+pub type Curve23Scalar = Fp<29>;
+
+/// Curve23 with the base point (0, 2) (of order 29, the whole group)
+pub fn curve23() -> Curve23 {
+    WeierstrassCurve::new(
+        FiniteField23Point::new(1),
+        FiniteField23Point::new(4),
+        Point::new(FiniteField23Point::new(0), FiniteField23Point::new(2)),
+        1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::EllipticCurve;
+
+    #[test]
+    fn test_eval_at() {
+        let c23 = curve23();
+
+        assert_eq!(
+            c23.eval_at(FiniteField23Point::new(0)),
+            Some((FiniteField23Point::new(2), FiniteField23Point::new(21)))
+        );
+        assert_eq!(
+            c23.eval_at(FiniteField23Point::new(1)),
+            Some((FiniteField23Point::new(11), FiniteField23Point::new(12)))
+        );
+        // 2^3 + 2 + 4 = 14 is not a square mod 23
+        assert_eq!(c23.eval_at(FiniteField23Point::new(2)), None);
+    }
+
+    #[test]
+    fn test_points() {
+        let c23 = curve23();
+
+        // 28 affine points + the point at infinity
+        let points: Vec<_> = c23.points().collect();
+        assert_eq!(points.len(), 29);
+        assert!(points.iter().all(|p| c23.is_on_curve(p)));
+
+        // The group order is prime: the base point generates every point
+        let g = c23.base_point();
+        let mut multiples = vec![];
+        let mut p = g;
+        while !p.is_identity() {
+            multiples.push(p);
+            p = c23.point_add(&p, &g);
+        }
+        assert_eq!(multiples.len(), 28);
+        assert!(multiples.iter().all(|p| points.contains(p)));
+    }
+
+    #[test]
+    fn test_order() {
+        let c23 = curve23();
+        assert_eq!(c23.group_order(), 29);
+        assert_eq!(c23.point_order(&c23.base_point()), 29);
+        assert_eq!(c23.order(), &[29]);
+        assert_eq!(
+            c23.cofactor(),
+            c23.group_order() / c23.point_order(&c23.base_point())
+        );
+    }
+
+    #[test]
+    fn test_point_add() {
+        let c23 = curve23();
+
+        let p = c23.base_point();
+        // 2P
+        let p2 = c23.point_add(&p, &p);
+        assert_eq!(
+            p2,
+            Point::new(FiniteField23Point::new(13), FiniteField23Point::new(12))
+        );
+
+        // 3P
+        let p3 = c23.point_add(&p2, &p);
+        assert_eq!(
+            p3,
+            Point::new(FiniteField23Point::new(11), FiniteField23Point::new(9))
+        );
+        assert_eq!(c23.point_mul(p, Curve23Scalar::new(3)), p3);
+    }
+
+    #[test]
+    fn test_point_mul_ladder() {
+        let c23 = curve23();
+        let p = c23.base_point();
+        for k in 0..Curve23Scalar::P {
+            let k = Curve23Scalar::new(k);
+            assert_eq!(c23.point_mul_ladder(p, k), c23.point_mul(p, k));
+        }
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_key_exchange() {
+        // Same as Curve61 test_key_exchange
+        let c23 = curve23();
+        let p = c23.base_point();
+
+        // Alice
+        let kA = Curve23Scalar::new(12);
+        let A = c23.point_mul(p, kA);
+
+        // Bob
+        let kB = Curve23Scalar::new(7);
+        let B = c23.point_mul(p, kB);
+
+        // Alice
+        let kA_B = c23.point_mul(B, kA);
+
+        // Bob
+        let kb_A = c23.point_mul(A, kB);
+
+        assert_eq!(kA_B, kb_A);
+        assert_eq!(
+            kA_B,
+            Point::new(FiniteField23Point::new(11), FiniteField23Point::new(14))
+        );
+    }
+}
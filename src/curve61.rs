@@ -1,122 +1,34 @@
-use crate::ff61::FiniteField;
+use crate::curve::Point;
+use crate::ff::{FiniteField, Fp};
 use crate::ff61::FiniteField61Point;
-
-use bitvec::prelude::*;
-
-trait EllipticCurve {
-    type ValueType: FiniteField;
-
-    fn base_point(&self) -> (Self::ValueType, Self::ValueType);
-    fn eval_at(&self, x: Self::ValueType) -> Option<(Self::ValueType, Self::ValueType)>;
-    fn point_add(
-        &self,
-        p: &(Self::ValueType, Self::ValueType),
-        q: &(Self::ValueType, Self::ValueType),
-    ) -> (Self::ValueType, Self::ValueType);
-    fn point_mul(
-        &self,
-        p: (Self::ValueType, Self::ValueType),
-        s: Self::ValueType,
-    ) -> Option<(Self::ValueType, Self::ValueType)>;
-}
-
-struct Curve61 {
-    base_point: (FiniteField61Point, FiniteField61Point),
-}
-
-impl EllipticCurve for Curve61 {
-    type ValueType = FiniteField61Point;
-
-    fn base_point(&self) -> (Self::ValueType, Self::ValueType) {
-        self.base_point.clone()
-    }
-
-    fn eval_at(&self, x: FiniteField61Point) -> Option<(FiniteField61Point, FiniteField61Point)> {
-        // Curve equation is: y^2 = x^3 + 9*x + 1
-        let x_pow_2 = &x * &x;
-        let x_pow_3 = &x_pow_2 * &x;
-        let x_mul_9 = &x * &FiniteField61Point::new(9);
-        let y_pow_2 = x_pow_3 + x_mul_9 + FiniteField61Point::new(1);
-        y_pow_2.square_root()
-    }
-
-    // FIXME: should handle add with infinity
-    fn point_add(
-        &self,
-        p: &(FiniteField61Point, FiniteField61Point),
-        q: &(FiniteField61Point, FiniteField61Point),
-    ) -> (FiniteField61Point, FiniteField61Point) {
-        // From: https://en.wikipedia.org/wiki/Elliptic_curve_point_multiplication#Point_addition
-
-        let lambda = match p == q {
-            true => {
-                let px_pow_2 = &p.0 * &p.0;
-                let num = &FiniteField61Point::new(3) * &px_pow_2 + FiniteField61Point::new(9);
-                let denom = &FiniteField61Point::new(2) * &p.1;
-                num * denom.mul_inverse()
-            }
-            false => {
-                let num = &q.1 - &p.1;
-                let denom = &q.0 - &p.0;
-                num * denom.mul_inverse()
-            }
-        };
-
-        let lambda_pow_2 = &lambda * &lambda;
-        let x3 = &(&lambda_pow_2 - &p.0) - &q.0;
-        let y3 = &(lambda * (&p.0 - &x3)) - &p.1;
-
-        (x3, y3)
-    }
-
-    fn point_mul(
-        &self,
-        p: (FiniteField61Point, FiniteField61Point),
-        s: FiniteField61Point,
-    ) -> Option<(FiniteField61Point, FiniteField61Point)> {
-        // From: https://en.wikipedia.org/wiki/Elliptic_curve_point_multiplication#Double-and-add
-
-        // Vector of bits
-        let num = s.value();
-        let raw = num.view_bits::<Lsb0>();
-        let bits = raw
-            .iter_ones()
-            .last()
-            .unwrap_or(bitvec::mem::bits_of::<u8>() - 1);
-        let bv = raw[..=bits].to_bitvec();
-
-        // res is set to point at infinity (aka None)
-        // Note that: P + INF = P & P_INF + P_INF = P_INF
-        let mut res: Option<(FiniteField61Point, FiniteField61Point)> = None;
-        let mut temp = p;
-
-        for bit in bv {
-            if bit {
-                // FIXME: remove this when point_add handles add with INF
-                res = if let Some(res_) = res {
-                    Some(self.point_add(&res_, &temp))
-                } else {
-                    Some(temp.clone())
-                }
-            }
-            temp = self.point_add(&temp, &temp);
-        }
-
-        res
-    }
+use crate::weierstrass::WeierstrassCurve;
+
+/// Curve61: y^2 = x^3 + 9x + 1 over 𝔽61
+pub type Curve61 = WeierstrassCurve<FiniteField61Point, Curve61Scalar>;
+
+/// Scalars of Curve61: integers mod 73 (the order of the base point)
+pub type Curve61Scalar = Fp<73>;
+
+/// Curve61 with the base point (5, 7) (of order 73, the whole group)
+pub fn curve61() -> Curve61 {
+    WeierstrassCurve::new(
+        FiniteField61Point::new(9),
+        FiniteField61Point::new(1),
+        Point::new(FiniteField61Point::new(5), FiniteField61Point::new(7)),
+        1,
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::curve::EllipticCurve;
 
     #[test]
     fn test_eval_at() {
         // From: https://curves.xargs.org/#elliptic-curves-and-finite-fields
 
-        let c61 = Curve61 {
-            base_point: (FiniteField61Point::new(5), FiniteField61Point::new(7)),
-        };
+        let c61 = curve61();
 
         assert_eq!(
             c61.eval_at(FiniteField61Point::new(0)),
@@ -139,49 +51,163 @@ mod tests {
     fn test_point_add() {
         // From: https://curves.xargs.org/#point-addition
 
-        let c61 = Curve61 {
-            base_point: (FiniteField61Point::new(5), FiniteField61Point::new(7)),
-        };
+        let c61 = curve61();
 
-        let p = (FiniteField61Point::new(5), FiniteField61Point::new(7));
+        let p = Point::new(FiniteField61Point::new(5), FiniteField61Point::new(7));
         // 2P
         let p2 = c61.point_add(&p, &p);
         assert_eq!(
             p2,
-            (FiniteField61Point::new(26), FiniteField61Point::new(50))
+            Point::new(FiniteField61Point::new(26), FiniteField61Point::new(50))
         );
 
         // 3P
         let p3 = c61.point_add(&p2, &p);
         assert_eq!(
             p3,
-            (FiniteField61Point::new(27), FiniteField61Point::new(38))
+            Point::new(FiniteField61Point::new(27), FiniteField61Point::new(38))
         );
     }
 
+    #[test]
+    fn test_point_add_identity() {
+        let c61 = curve61();
+
+        let p = c61.base_point();
+        let o = Point::Identity;
+        assert_eq!(c61.point_add(&o, &p), p);
+        assert_eq!(c61.point_add(&p, &o), p);
+        assert_eq!(c61.point_add(&o, &o), o);
+        assert_eq!(c61.point_add(&p, &c61.point_neg(&p)), o);
+        assert_eq!(c61.point_add(&c61.point_neg(&p), &p), o);
+        // Note: Curve61 has no point with y = 0 (x^3 + 9x + 1 has no root in 𝔽61)
+
+        assert_eq!(c61.point_mul(p, Curve61Scalar::new(0)), o);
+        assert_eq!(c61.point_mul(o, Curve61Scalar::new(5)), o);
+    }
+
     #[test]
     fn test_point_mul() {
         // From: https://curves.xargs.org/#efficient-point-multiplication
 
-        let c61 = Curve61 {
-            base_point: (FiniteField61Point::new(5), FiniteField61Point::new(7)),
-        };
+        let c61 = curve61();
 
-        let p = (FiniteField61Point::new(5), FiniteField61Point::new(7));
-        let p2 = c61.point_mul(p, FiniteField61Point::new(2));
+        let p = Point::new(FiniteField61Point::new(5), FiniteField61Point::new(7));
+        let p2 = c61.point_mul(p, Curve61Scalar::new(2));
         assert_eq!(
-            p2.unwrap(),
-            (FiniteField61Point::new(26), FiniteField61Point::new(50))
+            p2,
+            Point::new(FiniteField61Point::new(26), FiniteField61Point::new(50))
         );
 
-        let p = (FiniteField61Point::new(5), FiniteField61Point::new(7));
-        let p3 = c61.point_mul(p, FiniteField61Point::new(3));
+        let p = Point::new(FiniteField61Point::new(5), FiniteField61Point::new(7));
+        let p3 = c61.point_mul(p, Curve61Scalar::new(3));
         assert_eq!(
-            p3.unwrap(),
-            (FiniteField61Point::new(27), FiniteField61Point::new(38))
+            p3,
+            Point::new(FiniteField61Point::new(27), FiniteField61Point::new(38))
         );
     }
 
+    #[test]
+    fn test_point_mul_large_scalar() {
+        // Scalars are reduced mod 73 (the order of the base point) and not mod 61
+        let c61 = curve61();
+        let p = c61.base_point();
+        assert_eq!(c61.order(), &[73]);
+
+        let mut q = Point::Identity;
+        for k in 0..100 {
+            assert_eq!(c61.point_mul(p, Curve61Scalar::from_u64(k)), q);
+            q = c61.point_add(&q, &p);
+        }
+        assert_eq!(c61.point_mul(p, Curve61Scalar::new(72)), c61.point_neg(&p));
+
+        // 2^128 + 1 = 2^2 + 1 = 5 mod 73 (2^9 = 1 mod 73)
+        assert_eq!(
+            c61.point_mul(p, Curve61Scalar::from_le_limbs(&[1, 0, 1])),
+            c61.point_mul(p, Curve61Scalar::new(5))
+        );
+    }
+
+    #[test]
+    fn test_order() {
+        // The base point (5, 7) generates the whole group: 73 points (a prime) & h = 1
+        let c61 = curve61();
+        let p = c61.base_point();
+        assert_eq!(c61.point_order(&p), 73);
+        assert_eq!(c61.order(), &[c61.point_order(&p)]);
+        assert_eq!(c61.group_order(), 73);
+        assert_eq!(c61.cofactor(), c61.group_order() / c61.point_order(&p));
+
+        // Any other point also generates the whole group (73 is prime)
+        assert_eq!(c61.point_order(&Point::Identity), 1);
+        for k in 1..Curve61Scalar::P {
+            let q = c61.point_mul(p, Curve61Scalar::new(k));
+            assert_eq!(c61.point_order(&q), 73);
+        }
+    }
+
+    #[test]
+    fn test_points() {
+        let c61 = curve61();
+        let points: Vec<_> = c61.points().collect();
+        assert_eq!(points.len(), 73);
+        assert_eq!(points[0], Point::Identity);
+        assert!(points.iter().all(|p| c61.is_on_curve(p)));
+
+        // Group laws for every point (associativity for every triple)
+        for p in &points {
+            assert_eq!(c61.point_add(p, &Point::Identity), *p);
+            assert_eq!(c61.point_add(p, &c61.point_neg(p)), Point::Identity);
+            for q in &points {
+                let pq = c61.point_add(p, q);
+                assert_eq!(pq, c61.point_add(q, p));
+                for r in &points {
+                    assert_eq!(
+                        c61.point_add(&pq, r),
+                        c61.point_add(p, &c61.point_add(q, r))
+                    );
+                }
+            }
+        }
+
+        // Brute force: P + Q = R if P, Q & -R are the only points of the curve on the line
+        // through P & Q (the tangent if P = Q), no third point for a vertical line (Q = -P)
+        let f = FiniteField61Point::new;
+        let affine: Vec<_> = points.iter().filter_map(|p| p.coordinates()).collect();
+        for &(px, py) in &affine {
+            for &(qx, qy) in &affine {
+                let on_line = |&&(rx, ry): &&(FiniteField61Point, FiniteField61Point)| {
+                    match ((px, py) == (qx, qy), px == qx) {
+                        // 2y * (y' - y) = (3x^2 + 9) * (x' - x)
+                        (true, _) => f(2) * py * (ry - py) == (f(3) * px * px + f(9)) * (rx - px),
+                        (false, true) => rx == px,
+                        (false, false) => (qy - py) * (rx - px) == (ry - py) * (qx - px),
+                    }
+                };
+                let line: Vec<_> = affine.iter().filter(on_line).collect();
+
+                match c61.point_add(&Point::new(px, py), &Point::new(qx, qy)) {
+                    Point::Identity => assert!(line.iter().all(|(rx, _)| *rx == px)),
+                    Point::Affine(rx, ry) => {
+                        let expected = [(px, py), (qx, qy), (rx, -ry)];
+                        assert!(expected.iter().all(|r| line.contains(&r)));
+                        assert!(line.iter().all(|r| expected.contains(r)));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_point_mul_ladder() {
+        let c61 = curve61();
+        let p = c61.base_point();
+        for k in 0..Curve61Scalar::P {
+            let k = Curve61Scalar::new(k);
+            assert_eq!(c61.point_mul_ladder(p, k), c61.point_mul(p, k));
+        }
+    }
+
     #[allow(non_snake_case)]
     #[test]
     fn test_key_exchange() {
@@ -196,24 +222,22 @@ mod tests {
          * Bob computes the coordinates of kB*A, which is: kB*(kA*P)
          */
 
-        let c61 = Curve61 {
-            base_point: (FiniteField61Point::new(5), FiniteField61Point::new(7)),
-        };
+        let c61 = curve61();
         let p = c61.base_point();
 
         // Alice
-        let kA = FiniteField61Point::new(12); // A very random number :-)
-        let A = c61.point_mul(p.clone(), kA.clone()).unwrap();
+        let kA = Curve61Scalar::new(12); // A very random number :-)
+        let A = c61.point_mul(p, kA);
 
         // Bob
-        let kB = FiniteField61Point::new(7);
-        let B = c61.point_mul(p, kB.clone()).unwrap();
+        let kB = Curve61Scalar::new(7);
+        let B = c61.point_mul(p, kB);
 
         // Alice
-        let kA_B = c61.point_mul(B, kA).unwrap();
+        let kA_B = c61.point_mul(B, kA);
 
         // Bob
-        let kb_A = c61.point_mul(A, kB).unwrap();
+        let kb_A = c61.point_mul(A, kB);
 
         assert_eq!(kA_B, kb_A);
     }
@@ -0,0 +1,444 @@
+//! ECDSA signatures with deterministic nonces (RFC 6979) over any `EllipticCurve`
+//!
+//! From: https://www.secg.org/sec1-v2.pdf (section 4.1) & https://www.rfc-editor.org/rfc/rfc6979
+//!
+//! The private key d is a scalar, the public key is Q = d * G and messages are hashed with
+//! the hash function H (e.g. `Sha256`)
+
+use crate::bigint::{self, AsLimbs};
+use crate::curve::{EllipticCurve, Point};
+use crate::ff::FiniteField;
+use crate::hmac::{hmac, HashFunction};
+use std::marker::PhantomData;
+
+/// An ECDSA signature (r, s) with the recovery id v used to recover the public key:
+/// bit 0 set if y(R) is odd, bit 1 set if x(R) >= n (then r = x(R) - n)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Signature<S: FiniteField> {
+    pub r: S,
+    pub s: S,
+    pub v: u8,
+}
+
+/// Big endian bytes as little endian u64 limbs
+pub(crate) fn be_bytes_to_limbs(bytes: &[u8]) -> Vec<u64> {
+    bytes
+        .rchunks(8)
+        .map(|chunk| {
+            let mut buf = [0; 8];
+            buf[8 - chunk.len()..].copy_from_slice(chunk);
+            u64::from_be_bytes(buf)
+        })
+        .collect()
+}
+
+/// An integer (little endian limbs) as an element of F, None if not lower than the modulus
+fn from_limbs_exact<F: FiniteField>(limbs: &[u64]) -> Option<F> {
+    let trim = |l: &[u64]| l[..l.iter().rposition(|l| *l != 0).map_or(0, |i| i + 1)].to_vec();
+    let res = F::from_le_limbs(limbs);
+    (trim(res.value().as_limbs()) == trim(limbs)).then_some(res)
+}
+
+/// The leftmost qlen bits of the bytes as an integer (RFC 6979 section 2.3.2)
+fn bits2int(bytes: &[u8], qlen: u32) -> Vec<u64> {
+    let limbs = be_bytes_to_limbs(bytes);
+    let blen = 8 * bytes.len() as u32;
+    match blen > qlen {
+        true => bigint::shr(&limbs, blen - qlen),
+        false => limbs,
+    }
+}
+
+/// e: the message hash as a scalar (reduced mod n)
+fn hash_to_scalar<S: FiniteField, H: HashFunction>(message: &[u8]) -> S {
+    S::from_le_limbs(&bits2int(&H::digest(&[message]), bigint::bits(S::MODULUS)))
+}
+
+/// Deterministic nonces k in [1; n - 1] generated with HMAC-DRBG (RFC 6979 section 3.2)
+struct NonceGenerator<S: FiniteField, H: HashFunction> {
+    k: Vec<u8>,
+    v: Vec<u8>,
+    first: bool,
+    scalar: PhantomData<(S, H)>,
+}
+
+impl<S: FiniteField, H: HashFunction> NonceGenerator<S, H> {
+    fn new(d: &S, message: &[u8]) -> Self {
+        // int2octets(d) & bits2octets(H(m)) = int2octets(H(m) mod n)
+        let x = d.to_be_bytes();
+        let h = hash_to_scalar::<S, H>(message).to_be_bytes();
+
+        let v = vec![0x01; H::OUTPUT_LEN];
+        let k = hmac::<H>(&vec![0x00; H::OUTPUT_LEN], &[&v, &[0x00], &x, &h]);
+        let v = hmac::<H>(&k, &[&v]);
+        let k = hmac::<H>(&k, &[&v, &[0x01], &x, &h]);
+        let v = hmac::<H>(&k, &[&v]);
+
+        Self {
+            k,
+            v,
+            first: true,
+            scalar: PhantomData,
+        }
+    }
+
+    /// K = HMAC_K(V || 0x00) & V = HMAC_K(V): the state update after a rejected candidate
+    fn reseed(&mut self) {
+        self.k = hmac::<H>(&self.k, &[&self.v, &[0x00]]);
+        self.v = hmac::<H>(&self.k, &[&self.v]);
+    }
+
+    fn next(&mut self) -> S {
+        if !self.first {
+            self.reseed();
+        }
+        self.first = false;
+
+        let qlen = bigint::bits(S::MODULUS);
+        loop {
+            let mut t = Vec::new();
+            while 8 * (t.len() as u32) < qlen {
+                self.v = hmac::<H>(&self.k, &[&self.v]);
+                t.extend_from_slice(&self.v);
+            }
+
+            // The candidate must be in [1; n - 1] (no reduction mod n)
+            match from_limbs_exact::<S>(&bits2int(&t, qlen)) {
+                Some(k) if k != S::min() => return k,
+                _ => self.reseed(),
+            }
+        }
+    }
+}
+
+/// Public key: Q = d * G
+pub fn public_key<C: EllipticCurve>(c: &C, d: C::ScalarType) -> Point<C::ValueType> {
+    c.point_mul(c.base_point(), d)
+}
+
+/// Sign a message with the private key d (d != 0)
+///
+/// With `low_s`, s is replaced by n - s if s > n / 2: (r, s) & (r, n - s) are both valid
+/// signatures, allowing only the lowest one prevents signature malleability
+pub fn sign<C: EllipticCurve, H: HashFunction>(
+    c: &C,
+    d: C::ScalarType,
+    message: &[u8],
+    low_s: bool,
+) -> Signature<C::ScalarType> {
+    assert!(d != C::ScalarType::min(), "The private key must not be 0");
+
+    let e = hash_to_scalar::<C::ScalarType, H>(message);
+    let mut nonces = NonceGenerator::<C::ScalarType, H>::new(&d, message);
+
+    loop {
+        let k = nonces.next();
+
+        // R = k * G & r = x(R) mod n (try another k if r = 0 or s = 0)
+        let (x, y) = match c.point_mul(c.base_point(), k).coordinates() {
+            None => continue,
+            Some(xy) => xy,
+        };
+        let r = C::ScalarType::from_le_limbs(x.value().as_limbs());
+        if r == C::ScalarType::min() {
+            continue;
+        }
+
+        // Safe to unwrap: k != 0
+        let s = k.mul_inverse().unwrap() * (e + r * d);
+        if s == C::ScalarType::min() {
+            continue;
+        }
+
+        let x_overflow = from_limbs_exact::<C::ScalarType>(x.value().as_limbs()).is_none();
+        let mut v = y.is_odd() as u8 | (x_overflow as u8) << 1;
+
+        // n - s is the signature for -R (same x, opposite y parity)
+        if low_s && s.value() > (-s).value() {
+            v ^= 1;
+            return Signature { r, s: -s, v };
+        }
+        return Signature { r, s, v };
+    }
+}
+
+/// Verify a signature with the public key Q
+pub fn verify<C: EllipticCurve, H: HashFunction>(
+    c: &C,
+    q: &Point<C::ValueType>,
+    message: &[u8],
+    signature: &Signature<C::ScalarType>,
+) -> Result<(), &'static str> {
+    let Signature { r, s, .. } = *signature;
+    if r == C::ScalarType::min() || s == C::ScalarType::min() {
+        return Err("Invalid signature: r & s must be in [1; n - 1]");
+    }
+    if q.is_identity() {
+        return Err("Invalid public key");
+    }
+
+    // X = u1 * G + u2 * Q with u1 = e / s & u2 = r / s
+    let e = hash_to_scalar::<C::ScalarType, H>(message);
+    // Safe to unwrap: s != 0
+    let w = s.mul_inverse().unwrap();
+    let x = c.point_add(&c.point_mul(c.base_point(), e * w), &c.point_mul(*q, r * w));
+
+    match x.coordinates() {
+        Some((x, _)) if C::ScalarType::from_le_limbs(x.value().as_limbs()) == r => Ok(()),
+        _ => Err("Invalid signature"),
+    }
+}
+
+/// Recover the public key Q from a signature (r, s, v) & the signed message
+///
+/// With R the point (x, y) such that x = r (+ n if bit 1 of v is set) & y odd if bit 0 of v
+/// is set: Q = (s * R - e * G) / r
+pub fn recover_public_key<C: EllipticCurve, H: HashFunction>(
+    c: &C,
+    message: &[u8],
+    signature: &Signature<C::ScalarType>,
+) -> Result<Point<C::ValueType>, &'static str> {
+    let Signature { r, s, v } = *signature;
+    if r == C::ScalarType::min() || s == C::ScalarType::min() {
+        return Err("Invalid signature: r & s must be in [1; n - 1]");
+    }
+    if v > 3 {
+        return Err("Invalid recovery id");
+    }
+
+    let r_value = r.value();
+    let x = match v & 2 == 2 {
+        true => bigint::add(r_value.as_limbs(), C::ScalarType::MODULUS),
+        false => r_value.as_limbs().to_vec(),
+    };
+    let x = from_limbs_exact::<C::ValueType>(&x).ok_or("Invalid signature: x(R) >= p")?;
+    let (y1, y2) = c
+        .eval_at(x)
+        .ok_or("Invalid signature: R is not on the curve")?;
+    let y = if y1.is_odd() == (v & 1 == 1) { y1 } else { y2 };
+    let big_r = Point::new(x, y);
+
+    let e = hash_to_scalar::<C::ScalarType, H>(message);
+    // Safe to unwrap: r != 0
+    let r_inv = r.mul_inverse().unwrap();
+    let q = c.point_add(
+        &c.point_mul(big_r, s * r_inv),
+        &c.point_mul(c.base_point(), -e * r_inv),
+    );
+
+    match q.is_identity() {
+        true => Err("Invalid signature: Q is the identity"),
+        false => Ok(q),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bigint::Uint;
+    use crate::curve61::{curve61, Curve61Scalar};
+    use crate::ff61::FiniteField61Point;
+    use crate::nist::{p256, P256Field, P256Scalar};
+    use crate::secp256k1::{secp256k1, Secp256k1Scalar};
+    use crate::sha256::Sha256;
+
+    #[test]
+    fn test_rfc6979_p256() {
+        // From: RFC 6979 section A.2.5 (P-256 with SHA-256, checked with python)
+        let c = p256();
+        let d = P256Scalar::new(Uint::from_be_hex(
+            "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721",
+        ));
+        let q = public_key(&c, d);
+        assert_eq!(
+            q,
+            Point::new(
+                P256Field::new(Uint::from_be_hex(
+                    "60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6"
+                )),
+                P256Field::new(Uint::from_be_hex(
+                    "7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299"
+                )),
+            )
+        );
+
+        let vectors = [
+            (
+                "sample",
+                "a6e3c57dd01abe90086538398355dd4c3b17aa873382b0f24d6129493d8aad60",
+                "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716",
+                "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8",
+            ),
+            (
+                "test",
+                "d16b6ae827f17175e040871a1c7ec3500192c4c92677336ec2537acaee0008e0",
+                "f1abb023518351cd71d881567b1ea663ed3efcf6c5132b354f28d3b0b7d38367",
+                "019f4113742a2b14bd25926b49c649155f267e60d3814b4c0cc84250e46f0083",
+            ),
+        ];
+        let scalar = |hex| P256Scalar::new(Uint::from_be_hex(hex));
+
+        for (message, k, r, s) in vectors {
+            let message = message.as_bytes();
+            let mut nonces = NonceGenerator::<P256Scalar, Sha256>::new(&d, message);
+            assert_eq!(nonces.next(), scalar(k));
+
+            let signature = sign::<_, Sha256>(&c, d, message, false);
+            assert_eq!((signature.r, signature.s), (scalar(r), scalar(s)));
+            assert_eq!(verify::<_, Sha256>(&c, &q, message, &signature), Ok(()));
+            assert_eq!(
+                recover_public_key::<_, Sha256>(&c, message, &signature),
+                Ok(q)
+            );
+            assert!(verify::<_, Sha256>(&c, &q, b"other", &signature).is_err());
+        }
+    }
+
+    #[test]
+    fn test_secp256k1() {
+        // (d, message, r, s (low-S), v) computed with python (RFC 6979 reference algorithm,
+        // signatures checked with python cryptography)
+        let c = secp256k1();
+        let vectors = [
+            (
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "Satoshi Nakamoto",
+                "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8",
+                "2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5",
+                1,
+            ),
+            (
+                "c0ffee0123456789abcdef0123456789abcdef0123456789abcdef0123456789",
+                "sample",
+                "a9fc43046cea0ca437c20a3c914222f5e5bbea8000f5ce6917f51224df709f89",
+                "4e5dc18fc98992152a9cf4a691275d067957d177588627779efeb6caed8505ba",
+                1,
+            ),
+            (
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+                "test",
+                "65b79d53819915fe61f7f57d82134a73386e3f7fd0c791232f26fc1b942991e1",
+                "093a2c182134be3c4f39ac1f06ada089fcfcdd5d50f116bcfdc9e5e76e22a026",
+                1,
+            ),
+        ];
+        let scalar = |hex| Secp256k1Scalar::new(Uint::from_be_hex(hex));
+
+        for (d, message, r, s, v) in vectors {
+            let (d, message) = (scalar(d), message.as_bytes());
+            let q = public_key(&c, d);
+
+            let signature = sign::<_, Sha256>(&c, d, message, true);
+            assert_eq!(
+                signature,
+                Signature {
+                    r: scalar(r),
+                    s: scalar(s),
+                    v
+                }
+            );
+            assert_eq!(verify::<_, Sha256>(&c, &q, message, &signature), Ok(()));
+            assert_eq!(
+                recover_public_key::<_, Sha256>(&c, message, &signature),
+                Ok(q)
+            );
+
+            // Without low-S: either the same signature or (r, n - s) with the other parity
+            let high = sign::<_, Sha256>(&c, d, message, false);
+            assert!(high == signature || (high.s == -signature.s && high.v == signature.v ^ 1));
+            assert_eq!(verify::<_, Sha256>(&c, &q, message, &high), Ok(()));
+            assert_eq!(recover_public_key::<_, Sha256>(&c, message, &high), Ok(q));
+        }
+    }
+
+    #[test]
+    fn test_curve61() {
+        // Small enough to be checked by hand: (d, message, Q, (r, s, v)) computed with python
+        let c = curve61();
+        let f = FiniteField61Point::from_u64;
+        let s = Curve61Scalar::from_u64;
+        let vectors = [
+            (7, "sample", (52, 44), (48, 22, 0)),
+            (7, "test", (52, 44), (35, 72, 1)),
+            (42, "sample", (6, 24), (42, 27, 0)),
+            (42, "test", (6, 24), (6, 46, 1)),
+        ];
+
+        for (d, message, (qx, qy), (r, sig_s, v)) in vectors {
+            let q = public_key(&c, s(d));
+            assert_eq!(q, Point::new(f(qx), f(qy)));
+
+            let signature = sign::<_, Sha256>(&c, s(d), message.as_bytes(), false);
+            assert_eq!(
+                signature,
+                Signature {
+                    r: s(r),
+                    s: s(sig_s),
+                    v
+                }
+            );
+        }
+
+        // Every private key
+        let message = b"message";
+        let mut forgeries = 0;
+        for d in 1..73 {
+            let q = public_key(&c, s(d));
+            for low_s in [false, true] {
+                let signature = sign::<_, Sha256>(&c, s(d), message, low_s);
+                if low_s {
+                    assert!(signature.s.value() <= 36);
+                }
+                assert_eq!(verify::<_, Sha256>(&c, &q, message, &signature), Ok(()));
+                if verify::<_, Sha256>(&c, &q, b"massage", &signature).is_ok() {
+                    forgeries += 1;
+                }
+                assert_eq!(
+                    recover_public_key::<_, Sha256>(&c, message, &signature),
+                    Ok(q)
+                );
+            }
+        }
+        // With n = 73, a signature also matches another message (here: e = 32 instead of 12)
+        // when u1 * G + u2 * Q = ±R by chance: 2 out of 144 (computed with python)
+        assert_eq!(forgeries, 2);
+    }
+
+    #[test]
+    fn test_invalid() {
+        let c = curve61();
+        let s = Curve61Scalar::from_u64;
+        let q = public_key(&c, s(7));
+        let signature = sign::<_, Sha256>(&c, s(7), b"message", false);
+
+        let zero_r = Signature {
+            r: s(0),
+            ..signature
+        };
+        assert!(verify::<_, Sha256>(&c, &q, b"message", &zero_r).is_err());
+        assert!(recover_public_key::<_, Sha256>(&c, b"message", &zero_r).is_err());
+        let zero_s = Signature {
+            s: s(0),
+            ..signature
+        };
+        assert!(verify::<_, Sha256>(&c, &q, b"message", &zero_s).is_err());
+        assert!(verify::<_, Sha256>(&c, &Point::Identity, b"message", &signature).is_err());
+        // Another public key
+        let other = public_key(&c, s(8));
+        assert_eq!(
+            verify::<_, Sha256>(&c, &other, b"message", &signature),
+            Err("Invalid signature")
+        );
+        // Wrong recovery id: another public key (or none)
+        let wrong_v = Signature {
+            v: signature.v ^ 1,
+            ..signature
+        };
+        assert_ne!(
+            recover_public_key::<_, Sha256>(&c, b"message", &wrong_v),
+            Ok(q)
+        );
+        let invalid_v = Signature { v: 4, ..signature };
+        assert!(recover_public_key::<_, Sha256>(&c, b"message", &invalid_v).is_err());
+    }
+}
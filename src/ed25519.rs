@@ -0,0 +1,294 @@
+//! Ed25519 signatures (RFC 8032)
+//!
+//! From: https://www.rfc-editor.org/rfc/rfc8032#section-5.1
+//!
+//! Keys & signatures are byte arrays: 32 bytes secret key (aka seed), 32 bytes public key
+//! (the encoding of A = s * B) & 64 bytes signature (R || S)
+
+use crate::curve::{EllipticCurve, Point};
+use crate::edwards25519::{edwards25519, Ed25519Scalar, Edwards25519};
+use crate::ff::FiniteField;
+use crate::ff25519::FiniteField25519Point;
+use crate::sha512::Sha512;
+
+/// How the verification equation is checked (RFC 8032 allows both)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verification {
+    /// [S]B = R + [k]A and reject public keys & R of small order (a multiple of the cofactor 8)
+    Strict,
+    /// [8][S]B = [8]R + [8][k]A (the equation recommended by RFC 8032 section 5.1.7)
+    Cofactored,
+}
+
+/// Scalar from little endian bytes (reduced mod L)
+fn scalar_from_le_bytes(bytes: &[u8]) -> Ed25519Scalar {
+    let limbs: Vec<u64> = bytes
+        .chunks(8)
+        .map(|chunk| {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            u64::from_le_bytes(word)
+        })
+        .collect();
+    Ed25519Scalar::from_le_limbs(&limbs)
+}
+
+fn scalar_to_le_bytes(s: &Ed25519Scalar) -> Vec<u8> {
+    let mut bytes = s.to_be_bytes();
+    bytes.reverse();
+    bytes
+}
+
+/// SHA-512(R || A || M) mod L
+fn challenge(r: &[u8], a: &[u8], message: &[u8]) -> Ed25519Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(r).update(a).update(message);
+    scalar_from_le_bytes(&hasher.finalize())
+}
+
+/// 8 * P
+fn mul_by_cofactor(
+    c: &Edwards25519,
+    p: &Point<FiniteField25519Point>,
+) -> Point<FiniteField25519Point> {
+    (0..3).fold(*p, |p, _| c.point_double(&p))
+}
+
+/// Expand a secret key: the (clamped) secret scalar s & the prefix used to compute nonces
+///
+/// From: https://www.rfc-editor.org/rfc/rfc8032#section-5.1.5
+fn expand(secret: &[u8; 32]) -> (Ed25519Scalar, [u8; 32]) {
+    let mut hasher = Sha512::new();
+    hasher.update(secret);
+    let h = hasher.finalize();
+
+    // Clear the 3 lowest bits (multiple of the cofactor 8), clear bit 255 & set bit 254
+    let mut s = [0; 32];
+    s.copy_from_slice(&h[..32]);
+    s[0] &= 248;
+    s[31] &= 127;
+    s[31] |= 64;
+
+    let mut prefix = [0; 32];
+    prefix.copy_from_slice(&h[32..]);
+
+    // s > L: reduce it (L * B = O)
+    (scalar_from_le_bytes(&s), prefix)
+}
+
+/// Public key from a secret key: the encoding of A = s * B
+pub fn public_key(secret: &[u8; 32]) -> [u8; 32] {
+    let c = edwards25519();
+    let (s, _) = expand(secret);
+
+    let mut public = [0; 32];
+    public.copy_from_slice(&c.to_bytes(&c.point_mul(c.base_point(), s)));
+    public
+}
+
+/// Sign with the expanded secret key (s, prefix) & the public key encoding
+fn sign_expanded(s: Ed25519Scalar, prefix: &[u8; 32], public: &[u8], message: &[u8]) -> [u8; 64] {
+    let c = edwards25519();
+
+    // r = SHA-512(prefix || M) mod L & R = r * B
+    let mut hasher = Sha512::new();
+    hasher.update(prefix).update(message);
+    let r = scalar_from_le_bytes(&hasher.finalize());
+    let r_bytes = c.to_bytes(&c.point_mul(c.base_point(), r));
+
+    // S = (r + k * s) mod L
+    let k = challenge(&r_bytes, public, message);
+    let s = r + k * s;
+
+    let mut signature = [0; 64];
+    signature[..32].copy_from_slice(&r_bytes);
+    signature[32..].copy_from_slice(&scalar_to_le_bytes(&s));
+    signature
+}
+
+/// Sign a message (deterministic: the nonce is derived from the secret key & the message)
+///
+/// From: https://www.rfc-editor.org/rfc/rfc8032#section-5.1.6
+pub fn sign(secret: &[u8; 32], message: &[u8]) -> [u8; 64] {
+    let (s, prefix) = expand(secret);
+    sign_expanded(s, &prefix, &public_key(secret), message)
+}
+
+/// Verify a signature: return an error if the public key or the signature encoding is
+/// invalid or if the signature doesn't match
+///
+/// From: https://www.rfc-editor.org/rfc/rfc8032#section-5.1.7
+pub fn verify(
+    public: &[u8; 32],
+    message: &[u8],
+    signature: &[u8; 64],
+    mode: Verification,
+) -> Result<(), &'static str> {
+    let c = edwards25519();
+
+    let a = c.from_bytes(public).map_err(|_| "Invalid public key")?;
+    let r = c
+        .from_bytes(&signature[..32])
+        .map_err(|_| "Invalid signature: R is not a valid point")?;
+    // S must be canonical: S < L (to avoid malleability)
+    let mut s_bytes = signature[32..].to_vec();
+    s_bytes.reverse();
+    let s = Ed25519Scalar::from_be_bytes(&s_bytes).ok_or("Invalid signature: S >= L")?;
+
+    let k = challenge(&signature[..32], public, message);
+    let lhs = c.point_mul(c.base_point(), s);
+    let rhs = c.point_add(&r, &c.point_mul(a, k));
+
+    let valid = match mode {
+        Verification::Strict => {
+            if mul_by_cofactor(&c, &a).is_identity() {
+                return Err("Invalid public key: small order");
+            }
+            if mul_by_cofactor(&c, &r).is_identity() {
+                return Err("Invalid signature: R has a small order");
+            }
+            lhs == rhs
+        }
+        Verification::Cofactored => mul_by_cofactor(&c, &lhs) == mul_by_cofactor(&c, &rhs),
+    };
+
+    match valid {
+        true => Ok(()),
+        false => Err("Invalid signature"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn to_array<const N: usize>(bytes: &[u8]) -> [u8; N] {
+        bytes.try_into().unwrap()
+    }
+
+    #[test]
+    fn test_rfc8032() {
+        // From: RFC 8032 section 7.1 (TEST 1, 2, 3 & SHA(abc))
+        // (secret key, public key, message, signature)
+        let vectors = [
+            (
+                "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+                "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+                "",
+                "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+            ),
+            (
+                "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+                "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+                "72",
+                "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+            ),
+            (
+                "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+                "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+                "af82",
+                "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+            ),
+            (
+                "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42",
+                "ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf",
+                "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+                "dc2a4459e7369633a52b1bf277839a00201009a3efbf3ecb69bea2186c26b58909351fc9ac90b3ecfdfbc7c66431e0303dca179c138ac17ad9bef1177331a704",
+            ),
+        ];
+
+        for (secret, public, message, signature) in vectors {
+            let secret = to_array(&from_hex(secret));
+            let public = to_array(&from_hex(public));
+            let message = from_hex(message);
+            let signature = to_array(&from_hex(signature));
+
+            assert_eq!(public_key(&secret), public);
+            assert_eq!(sign(&secret, &message), signature);
+            for mode in [Verification::Strict, Verification::Cofactored] {
+                assert_eq!(verify(&public, &message, &signature, mode), Ok(()));
+
+                // Another message or a modified signature
+                let mut other = message.clone();
+                other.push(0);
+                assert!(verify(&public, &other, &signature, mode).is_err());
+                let mut modified = signature;
+                modified[40] ^= 1;
+                assert!(verify(&public, &message, &modified, mode).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_canonical_s() {
+        // S + L is another representation of S: it must be rejected
+        let secret = [7; 32];
+        let public = public_key(&secret);
+        let mut signature = sign(&secret, b"message");
+
+        let l = scalar_to_le_bytes(&Ed25519Scalar::max());
+        let mut carry = 1u16;
+        for (s, l) in signature[32..].iter_mut().zip(l) {
+            let sum = *s as u16 + l as u16 + carry;
+            *s = sum as u8;
+            carry = sum >> 8;
+        }
+        for mode in [Verification::Strict, Verification::Cofactored] {
+            assert_eq!(
+                verify(&public, b"message", &signature, mode),
+                Err("Invalid signature: S >= L")
+            );
+        }
+    }
+
+    #[test]
+    fn test_small_order() {
+        let c = edwards25519();
+        // T = (0, -1): a point of order 2
+        let t = Point::new(FiniteField25519Point::min(), -FiniteField25519Point::one());
+        assert!(c.point_double(&t).is_identity());
+
+        // The identity as public key & R with S = 0: [S]B = R + [k]A for any message
+        let mut identity = [0; 32];
+        identity[0] = 1;
+        let mut signature = [0; 64];
+        signature[..32].copy_from_slice(&identity);
+        assert_eq!(
+            verify(&identity, b"message", &signature, Verification::Strict),
+            Err("Invalid public key: small order")
+        );
+        assert_eq!(
+            verify(&identity, b"message", &signature, Verification::Cofactored),
+            Ok(())
+        );
+
+        // A public key with a small order component: A' = A + T
+        // Only the cofactored equation holds when k * T != O (k odd)
+        let (s, prefix) = expand(&[7; 32]);
+        let a = c.point_add(&c.point_mul(c.base_point(), s), &t);
+        let public: [u8; 32] = to_array(&c.to_bytes(&a));
+        let message = (0u8..)
+            .map(|i| vec![i])
+            .find(|m| {
+                let signature = sign_expanded(s, &prefix, &public, m);
+                challenge(&signature[..32], &public, m).value().limbs()[0] % 2 == 1
+            })
+            .unwrap();
+        let signature = sign_expanded(s, &prefix, &public, &message);
+        assert_eq!(
+            verify(&public, &message, &signature, Verification::Strict),
+            Err("Invalid signature")
+        );
+        assert_eq!(
+            verify(&public, &message, &signature, Verification::Cofactored),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,502 @@
+//! Elliptic curves in twisted Edwards form: a*x^2 + y^2 = 1 + d*x^2*y^2
+//!
+//! From: https://en.wikipedia.org/wiki/Twisted_Edwards_curve
+//!
+//! The neutral element is the affine point (0, 1): it is mapped to `Point::Identity` so the
+//! curves can be used through the `EllipticCurve` trait like the Weierstrass ones
+
+use crate::bigint::AsLimbs;
+use crate::curve::{affine_points, montgomery_ladder, EllipticCurve, Point};
+use crate::ff::FiniteField;
+use std::marker::PhantomData;
+
+use bitvec::prelude::*;
+
+/// A point in extended coordinates: (X : Y : Z : T) is the affine point (X / Z, Y / Z)
+/// with T = X * Y / Z (the identity is (0 : 1 : 1 : 0))
+///
+/// From: https://eprint.iacr.org/2008/522.pdf (Hisil, Wong, Carter & Dawson)
+#[derive(Debug, Clone, Copy)]
+pub struct ExtendedPoint<F: FiniteField> {
+    x: F,
+    y: F,
+    z: F,
+    t: F,
+}
+
+impl<F: FiniteField> ExtendedPoint<F> {
+    pub fn identity() -> Self {
+        Self {
+            x: F::min(),
+            y: F::one(),
+            z: F::one(),
+            t: F::min(),
+        }
+    }
+
+    pub fn from_affine(p: &Point<F>) -> Self {
+        match p {
+            Point::Identity => Self::identity(),
+            Point::Affine(x, y) => Self {
+                x: *x,
+                y: *y,
+                z: F::one(),
+                t: *x * *y,
+            },
+        }
+    }
+
+    pub fn to_affine(&self) -> Point<F> {
+        // Safe to unwrap: Z is never 0 for points on the curve (the formulas are complete)
+        let z_inv = self.z.mul_inverse().unwrap();
+        let (x, y) = (self.x * z_inv, self.y * z_inv);
+        if x == F::min() && y == F::one() {
+            Point::Identity
+        } else {
+            Point::new(x, y)
+        }
+    }
+}
+
+impl<F: FiniteField> PartialEq for ExtendedPoint<F> {
+    /// Same affine point: X1 * Z2 = X2 * Z1 & Y1 * Z2 = Y2 * Z1
+    fn eq(&self, other: &Self) -> bool {
+        self.x * other.z == other.x * self.z && self.y * other.z == other.y * self.z
+    }
+}
+
+/// A twisted Edwards curve a*x^2 + y^2 = 1 + d*x^2*y^2 over the finite field F
+/// with a base point of prime order n (and h = #E / n the cofactor)
+///
+/// Scalars are elements of S, the prime field 𝔽n (n is S::MODULUS)
+///
+/// Note: the addition law is complete (no exceptional case) as a is a square & d is not
+/// (`try_new` rejects the other curves)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TwistedEdwardsCurve<F: FiniteField, S: FiniteField> {
+    a: F,
+    d: F,
+    /// 2 * d (used by the addition for a = -1)
+    k: F,
+    base_point: Point<F>,
+    cofactor: u64,
+    /// a = -1 (as for edwards25519): the addition can save a multiplication
+    a_is_minus_1: bool,
+    scalar: PhantomData<S>,
+}
+
+impl<F: FiniteField, S: FiniteField> TwistedEdwardsCurve<F, S> {
+    pub fn try_new(a: F, d: F, base_point: Point<F>, cofactor: u64) -> Result<Self, &'static str> {
+        // The curve must be non singular: a * d * (a - d) != 0
+        if a == F::min() || d == F::min() || a == d {
+            return Err("Curve is singular: a * d * (a - d) = 0");
+        }
+        // The formulas are complete (`ExtendedPoint::to_affine` never divides by 0) only if
+        // a is a square & d is not
+        if !a.is_square() {
+            return Err("Incomplete addition law: a is not a square");
+        }
+        if d.is_square() {
+            return Err("Incomplete addition law: d is a square");
+        }
+
+        let curve = Self::new_unchecked(a, d, base_point, cofactor);
+        if !curve.is_on_curve(&base_point) {
+            return Err("Base point is not on the curve");
+        }
+        // The order of the base point must be n = S::MODULUS (a prime): (n - 1) * G = -G
+        if base_point.is_identity() {
+            return Err("Base point is the identity");
+        }
+        if curve.point_mul(base_point, S::max()) != curve.point_neg(&base_point) {
+            return Err("Base point order is not the scalar field modulus");
+        }
+
+        Ok(curve)
+    }
+
+    pub fn new(a: F, d: F, base_point: Point<F>, cofactor: u64) -> Self {
+        Self::try_new(a, d, base_point, cofactor)
+            .unwrap_or_else(|e| panic!("Cannot init a TwistedEdwardsCurve: {}", e))
+    }
+
+    /// No check at all (those of `try_new` cost a full scalar multiplication): only for the
+    /// standard curves of this crate, whose tests check the parameters with `try_new`
+    pub(crate) fn new_unchecked(a: F, d: F, base_point: Point<F>, cofactor: u64) -> Self {
+        Self {
+            a,
+            d,
+            k: d + d,
+            base_point,
+            cofactor,
+            a_is_minus_1: a == -F::one(),
+            scalar: PhantomData,
+        }
+    }
+
+    pub fn a(&self) -> F {
+        self.a
+    }
+
+    pub fn d(&self) -> F {
+        self.d
+    }
+
+    /// Order of the base point (little endian u64 limbs)
+    pub fn order(&self) -> &'static [u64] {
+        S::MODULUS
+    }
+
+    /// Return true if the point satisfies the curve equation (the identity is on every curve)
+    pub fn is_on_curve(&self, p: &Point<F>) -> bool {
+        match p {
+            Point::Identity => true,
+            Point::Affine(x, y) => {
+                let (xx, yy) = (x.square(), y.square());
+                self.a * xx + yy == F::one() + self.d * xx * yy
+            }
+        }
+    }
+
+    /// P + Q in extended coordinates: the same (unified) formula is valid for doublings
+    ///
+    /// From: https://hyperelliptic.org/EFD/g1p/auto-twisted-extended.html#addition-add-2008-hwcd
+    /// (and #addition-add-2008-hwcd-3 for a = -1)
+    pub fn extended_add(&self, p: &ExtendedPoint<F>, q: &ExtendedPoint<F>) -> ExtendedPoint<F> {
+        let (e, f, g, h) = if self.a_is_minus_1 {
+            let a = (p.y - p.x) * (q.y - q.x);
+            let b = (p.y + p.x) * (q.y + q.x);
+            let c = p.t * self.k * q.t;
+            let d = (p.z + p.z) * q.z;
+            (b - a, d - c, d + c, b + a)
+        } else {
+            let a = p.x * q.x;
+            let b = p.y * q.y;
+            let c = p.t * self.d * q.t;
+            let d = p.z * q.z;
+            let e = (p.x + p.y) * (q.x + q.y) - a - b;
+            (e, d - c, d + c, b - self.a * a)
+        };
+
+        ExtendedPoint {
+            x: e * f,
+            y: g * h,
+            z: f * g,
+            t: e * h,
+        }
+    }
+
+    /// 2 * P in extended coordinates (T is not needed)
+    ///
+    /// From: https://hyperelliptic.org/EFD/g1p/auto-twisted-extended.html#doubling-dbl-2008-hwcd
+    pub fn extended_double(&self, p: &ExtendedPoint<F>) -> ExtendedPoint<F> {
+        let a = p.x.square();
+        let b = p.y.square();
+        let c = F::from_u64(2) * p.z.square();
+        let d = self.a * a;
+        let e = (p.x + p.y).square() - a - b;
+        let g = d + b;
+        let f = g - c;
+        let h = d - b;
+
+        ExtendedPoint {
+            x: e * f,
+            y: g * h,
+            z: f * g,
+            t: e * h,
+        }
+    }
+
+    /// Length of an encoded point: the bits of y plus 1 bit for the sign of x
+    pub fn encoded_len() -> usize {
+        (crate::bigint::bits(F::MODULUS) as usize + 1).div_ceil(8)
+    }
+
+    /// Encode a point as y (little endian) with the "sign" of x (x odd) in the highest bit
+    ///
+    /// From: https://www.rfc-editor.org/rfc/rfc8032#section-5.1.2
+    pub fn to_bytes(&self, p: &Point<F>) -> Vec<u8> {
+        let (x, y) = p.coordinates().unwrap_or((F::min(), F::one()));
+
+        let mut bytes = y.to_be_bytes();
+        bytes.reverse();
+        bytes.resize(Self::encoded_len(), 0);
+        if x.is_odd() {
+            *bytes.last_mut().unwrap() |= 0x80;
+        }
+        bytes
+    }
+
+    /// Decode (aka decompress) a point: recover x from y & the sign of x
+    /// Return an error if the encoding is invalid or if the point is not on the curve
+    ///
+    /// From: https://www.rfc-editor.org/rfc/rfc8032#section-5.1.3
+    pub fn from_bytes(&self, bytes: &[u8]) -> Result<Point<F>, &'static str> {
+        if bytes.len() != Self::encoded_len() {
+            return Err("Invalid encoding length");
+        }
+
+        let mut y_bytes = bytes.to_vec();
+        let x_odd = y_bytes.last().unwrap() & 0x80 != 0;
+        *y_bytes.last_mut().unwrap() &= 0x7f;
+        y_bytes.reverse();
+        // Remove the extra byte (if any): it must be 0
+        let extra = y_bytes.len() - F::byte_len();
+        if y_bytes[..extra].iter().any(|b| *b != 0) {
+            return Err("Invalid y coordinate");
+        }
+        let y = F::from_be_bytes(&y_bytes[extra..]).ok_or("Invalid y coordinate")?;
+
+        // x^2 = (y^2 - 1) / (d * y^2 - a)
+        let yy = y.square();
+        let x = (yy - F::one()) * (self.d * yy - self.a).mul_inverse().unwrap_or(F::min());
+        let (x1, x2) = x.square_root().ok_or("Point is not on the curve")?;
+
+        // Both roots have a different parity (x and p - x with p odd) unless x = 0
+        let x = if x1.is_odd() == x_odd { x1 } else { x2 };
+        if x.is_odd() != x_odd {
+            return Err("Invalid x sign");
+        }
+
+        let p = match x == F::min() && y == F::one() {
+            true => Point::Identity,
+            false => Point::new(x, y),
+        };
+        match self.is_on_curve(&p) {
+            true => Ok(p),
+            false => Err("Point is not on the curve"),
+        }
+    }
+}
+
+impl<F: FiniteField, S: FiniteField> EllipticCurve for TwistedEdwardsCurve<F, S> {
+    type ValueType = F;
+    type ScalarType = S;
+
+    fn base_point(&self) -> Point<F> {
+        self.base_point
+    }
+
+    /// y such that: y^2 = (1 - a * x^2) / (1 - d * x^2)
+    fn eval_at(&self, x: F) -> Option<(F, F)> {
+        let xx = x.square();
+        let denom = (F::one() - self.d * xx).mul_inverse()?;
+        ((F::one() - self.a * xx) * denom).square_root()
+    }
+
+    /// The affine identity (0, 1) is only listed once: as `Point::Identity`
+    fn points(&self) -> impl Iterator<Item = Point<F>> + '_ {
+        let identity = Point::new(F::min(), F::one());
+        std::iter::once(Point::Identity).chain(affine_points(self).filter(move |p| *p != identity))
+    }
+
+    fn point_add(&self, p: &Point<F>, q: &Point<F>) -> Point<F> {
+        self.extended_add(
+            &ExtendedPoint::from_affine(p),
+            &ExtendedPoint::from_affine(q),
+        )
+        .to_affine()
+    }
+
+    /// -(x, y) = (-x, y)
+    fn point_neg(&self, p: &Point<F>) -> Point<F> {
+        match p {
+            Point::Identity => Point::Identity,
+            Point::Affine(x, y) => Point::new(-*x, *y),
+        }
+    }
+
+    fn point_double(&self, p: &Point<F>) -> Point<F> {
+        self.extended_double(&ExtendedPoint::from_affine(p))
+            .to_affine()
+    }
+
+    fn point_mul(&self, p: Point<F>, s: S) -> Point<F> {
+        // Double-and-add from the most significant bit (in extended coordinates)
+        let p = ExtendedPoint::from_affine(&p);
+
+        let num = s.value();
+        let bits = num.as_limbs().view_bits::<Lsb0>();
+
+        let mut res = ExtendedPoint::identity();
+
+        for bit in bits[..bits.last_one().map_or(0, |i| i + 1)].iter().rev() {
+            res = self.extended_double(&res);
+            if *bit {
+                res = self.extended_add(&res, &p);
+            }
+        }
+
+        res.to_affine()
+    }
+
+    /// Montgomery ladder in extended coordinates (the formulas are complete, see `try_new`)
+    fn point_mul_ladder(&self, p: Point<F>, s: S) -> Point<F> {
+        montgomery_ladder(
+            s,
+            ExtendedPoint::from_affine(&p),
+            ExtendedPoint::identity(),
+            |p, q| self.extended_add(p, q),
+            |p| self.extended_double(p),
+        )
+        .to_affine()
+    }
+
+    /// The cofactor given to `new` (no enumeration)
+    fn cofactor(&self) -> u64 {
+        self.cofactor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ff::Fp;
+
+    type F13 = Fp<13>;
+    type S2 = Fp<2>;
+    type S3 = Fp<3>;
+
+    /// a = 1 (a square) & d = 2 (not a square mod 13): the addition law is complete
+    /// 8 points (a cyclic group) & the base point (0, -1) of order 2 (h = 4)
+    fn curve() -> TwistedEdwardsCurve<F13, S2> {
+        TwistedEdwardsCurve::new(
+            F13::new(1),
+            F13::new(2),
+            Point::new(F13::new(0), -F13::one()),
+            4,
+        )
+    }
+
+    /// Affine addition law
+    fn add_affine<S: FiniteField>(
+        c: &TwistedEdwardsCurve<F13, S>,
+        p: &Point<F13>,
+        q: &Point<F13>,
+    ) -> Point<F13> {
+        let (x1, y1) = p.coordinates().unwrap_or((F13::new(0), F13::new(1)));
+        let (x2, y2) = q.coordinates().unwrap_or((F13::new(0), F13::new(1)));
+        let dxy = c.d() * x1 * x2 * y1 * y2;
+        let x3 = (x1 * y2 + y1 * x2) * (F13::one() + dxy).mul_inverse().unwrap();
+        let y3 = (y1 * y2 - c.a() * x1 * x2) * (F13::one() - dxy).mul_inverse().unwrap();
+        match x3 == F13::new(0) && y3 == F13::new(1) {
+            true => Point::Identity,
+            false => Point::new(x3, y3),
+        }
+    }
+
+    fn check_curve<S: FiniteField>(c: &TwistedEdwardsCurve<F13, S>) {
+        let points: Vec<_> = c.points().collect();
+        for p in &points {
+            assert!(c.is_on_curve(p));
+            assert_eq!(c.point_add(p, &Point::Identity), *p);
+            assert_eq!(c.point_add(p, &c.point_neg(p)), Point::Identity);
+            assert_eq!(c.point_double(p), add_affine(c, p, p));
+            assert_eq!(c.from_bytes(&c.to_bytes(p)), Ok(*p));
+            for q in &points {
+                assert_eq!(c.point_add(p, q), add_affine(c, p, q));
+            }
+        }
+    }
+
+    #[test]
+    fn test_addition() {
+        check_curve(&curve());
+        // a = -1 (a square as 13 = 1 mod 4) & d = 5 (not a square): 12 points & the base point
+        // (4, 10) of order 3 (h = 4)
+        check_curve(&TwistedEdwardsCurve::<F13, S3>::new(
+            -F13::new(1),
+            F13::new(5),
+            Point::new(F13::new(4), F13::new(10)),
+            4,
+        ));
+    }
+
+    #[test]
+    fn test_point_mul_ladder() {
+        // Every point & every scalar mod 11 (no base point order to check) on the curves of
+        // test_addition
+        let base_point = curve().base_point();
+        for (a, d) in [(F13::new(1), F13::new(2)), (-F13::new(1), F13::new(5))] {
+            let c = TwistedEdwardsCurve::<F13, Fp<11>>::new_unchecked(a, d, base_point, 4);
+            for p in c.points() {
+                for k in 0..11 {
+                    let k = Fp::<11>::new(k);
+                    assert_eq!(c.point_mul_ladder(p, k), c.point_mul(p, k));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_order() {
+        // 8 points with the identity (0, 1) counted once
+        let c = curve();
+        let points: Vec<_> = c.points().collect();
+        assert_eq!(c.group_order(), 8);
+        assert_eq!(points.len(), 8);
+        assert_eq!(c.point_order(&c.base_point()), 2);
+        assert_eq!(
+            c.cofactor(),
+            c.group_order() / c.point_order(&c.base_point())
+        );
+
+        // (0, -1) has order 2 & (order, number of points) computed with python
+        assert_eq!(c.point_order(&Point::new(F13::new(0), -F13::one())), 2);
+        for (order, count) in [(1, 1), (2, 1), (4, 2), (8, 4)] {
+            let n = points.iter().filter(|p| c.point_order(p) == order).count();
+            assert_eq!(n, count);
+        }
+    }
+
+    #[test]
+    fn test_try_new() {
+        let c = TwistedEdwardsCurve::<F13, S2>::try_new;
+        assert!(c(F13::new(0), F13::new(2), Point::Identity, 1).is_err());
+        assert!(c(F13::new(1), F13::new(0), Point::Identity, 1).is_err());
+        assert!(c(F13::new(3), F13::new(3), Point::Identity, 1).is_err());
+        // Incomplete addition law: a = 2 is not a square or d = 4 is a square mod 13
+        let base_point = curve().base_point();
+        assert_eq!(
+            c(F13::new(2), F13::new(5), base_point, 4),
+            Err("Incomplete addition law: a is not a square")
+        );
+        assert_eq!(
+            c(F13::new(1), F13::new(4), base_point, 4),
+            Err("Incomplete addition law: d is a square")
+        );
+        assert!(c(
+            F13::new(1),
+            F13::new(2),
+            Point::new(F13::new(1), F13::new(1)),
+            1
+        )
+        .is_err());
+        // The base point must have the order 2 (the modulus of S2): not O & not (1, 0) (order 4)
+        assert!(c(F13::new(1), F13::new(2), Point::Identity, 4).is_err());
+        assert!(c(
+            F13::new(1),
+            F13::new(2),
+            Point::new(F13::new(1), F13::new(0)),
+            2
+        )
+        .is_err());
+        assert!(c(F13::new(1), F13::new(2), curve().base_point(), 4).is_ok());
+    }
+
+    #[test]
+    fn test_encoding() {
+        let c = curve();
+        // 4 bits for y & 1 bit for the sign of x
+        assert_eq!(TwistedEdwardsCurve::<F13, S2>::encoded_len(), 1);
+        assert_eq!(c.to_bytes(&Point::Identity), vec![1]);
+        assert_eq!(c.from_bytes(&[1]), Ok(Point::Identity));
+
+        // x = 0 has no "negative" version
+        assert!(c.from_bytes(&[0x81]).is_err());
+        // y = 13 is not a valid coordinate
+        assert!(c.from_bytes(&[13]).is_err());
+        assert!(c.from_bytes(&[]).is_err());
+        assert!(c.from_bytes(&[1, 0]).is_err());
+    }
+}
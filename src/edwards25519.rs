@@ -0,0 +1,146 @@
+//! edwards25519: the twisted Edwards curve -x^2 + y^2 = 1 + d*x^2*y^2 with d = -121665/121666
+//! over 𝔽p (p = 2^255 - 19), birationally equivalent to Curve25519
+//!
+//! From: https://www.rfc-editor.org/rfc/rfc8032#section-5.1 & https://www.rfc-editor.org/rfc/rfc7748#section-4.1
+
+use crate::bigint::Uint;
+use crate::curve::Point;
+use crate::edwards::TwistedEdwardsCurve;
+use crate::ff::FiniteField;
+use crate::ff25519::FiniteField25519Point;
+use crate::ff_big::PrimeModulus;
+use crate::ff_mont::MontFp;
+
+/// L = 2^252 + 27742317777372353535851937790883648493: the order of the base point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ed25519L;
+impl PrimeModulus<4> for Ed25519L {
+    const P: Uint<4> =
+        Uint::from_be_hex("1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed");
+}
+
+/// Scalar field 𝔽L
+pub type Ed25519Scalar = MontFp<Ed25519L, 4>;
+
+pub type Edwards25519 = TwistedEdwardsCurve<FiniteField25519Point, Ed25519Scalar>;
+
+/// edwards25519 with its standard base point B = (x, 4/5) (x "positive": even), cofactor 8
+pub fn edwards25519() -> Edwards25519 {
+    // Checked by test_constants: the base point is on the curve with the order L
+    TwistedEdwardsCurve::new_unchecked(
+        -FiniteField25519Point::one(),
+        FiniteField25519Point::new(Uint::from_be_hex(
+            "52036cee2b6ffe738cc740797779e89800700a4d4141d8ab75eb4dca135978a3",
+        )),
+        Point::new(
+            FiniteField25519Point::new(Uint::from_be_hex(
+                "216936d3cd6e53fec0a4e231fdd6dc5c692cc7609525a7b2c9562d608f25d51a",
+            )),
+            FiniteField25519Point::new(Uint::from_be_hex(
+                "6666666666666666666666666666666666666666666666666666666666666658",
+            )),
+        ),
+        8,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::EllipticCurve;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_constants() {
+        let c = edwards25519();
+        let f = FiniteField25519Point::from_u64;
+
+        // d = -121665 / 121666 & y(B) = 4 / 5
+        assert_eq!(c.d() * f(121666), -f(121665));
+        let (x, y) = c.base_point().coordinates().unwrap();
+        assert_eq!(y * f(5), f(4));
+        assert!(!x.is_odd());
+
+        // (L - 1) * B = -B so L * B = O
+        let b = c.base_point();
+        assert_eq!(c.point_mul(b, Ed25519Scalar::max()), c.point_neg(&b));
+        assert_eq!(c.point_add(&b, &c.point_neg(&b)), Point::Identity);
+
+        // edwards25519() skips the checks of try_new
+        assert_eq!(Edwards25519::try_new(c.a(), c.d(), b, 8), Ok(c));
+    }
+
+    #[test]
+    fn test_encoding() {
+        let c = edwards25519();
+
+        // From: RFC 8032 section 5.1 (B encoding: y = 4/5 with x even)
+        let b = c.base_point();
+        assert_eq!(
+            c.to_bytes(&b),
+            from_hex("5866666666666666666666666666666666666666666666666666666666666666")
+        );
+        assert_eq!(c.from_bytes(&c.to_bytes(&b)), Ok(b));
+        assert_eq!(
+            c.from_bytes(&c.to_bytes(&c.point_neg(&b))),
+            Ok(c.point_neg(&b))
+        );
+
+        let mut identity = vec![0; 32];
+        identity[0] = 1;
+        assert_eq!(c.to_bytes(&Point::Identity), identity);
+        assert_eq!(c.from_bytes(&identity), Ok(Point::Identity));
+        // x = 0 has no "negative" version
+        identity[31] |= 0x80;
+        assert_eq!(c.from_bytes(&identity), Err("Invalid x sign"));
+
+        // y = p is not canonical
+        let bytes = from_hex("edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f");
+        assert_eq!(c.from_bytes(&bytes), Err("Invalid y coordinate"));
+        // y = 2: (y^2 - 1) / (d * y^2 + 1) is not a square (computed with python)
+        let mut bytes = vec![0; 32];
+        bytes[0] = 2;
+        assert_eq!(c.from_bytes(&bytes), Err("Point is not on the curve"));
+        assert_eq!(c.from_bytes(&bytes[1..]), Err("Invalid encoding length"));
+    }
+
+    #[test]
+    fn test_public_keys() {
+        // (clamped secret scalar a (big endian), encoding of A = a * B)
+        // computed with python hashlib & cryptography from the RFC 8032 section 7.1 TEST 1 & 2
+        // secret keys, then 00..00 & ff..ff
+        let c = edwards25519();
+        let vectors = [
+            (
+                "4fe94d9006f020a5a3c080d96827fffd3c010ac0f12e7a42cb33284f86837c30",
+                "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            ),
+            (
+                "512e502eb0249a255e1c827f3b6b6c7f0a79f4ca8575a91528d58258d79ebd68",
+                "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+            ),
+            (
+                "56f1874a926079f5907a26b57079b5583e42c3d0fdbb2b7b8638a8dbc1ad4650",
+                "3b6a27bcceb6a42d62a3a8d02a6f0d73653215771de243a63ac048a18b59da29",
+            ),
+            (
+                "570b3d7cbb8e1559461ca341ca5140306489bdab5fdd749da71647863569cd20",
+                "76a1592044a6e4f511265bca73a604d90b0529d1df602be30a19a9257660d1f5",
+            ),
+        ];
+
+        for (a, public) in vectors {
+            // a > L: reduce it (L * B = O)
+            let a = Ed25519Scalar::from_le_limbs(Uint::<4>::from_be_hex(a).limbs());
+            let p = c.point_mul(c.base_point(), a);
+            assert_eq!(c.to_bytes(&p), from_hex(public));
+            assert_eq!(c.from_bytes(&from_hex(public)), Ok(p));
+        }
+    }
+}
@@ -0,0 +1,412 @@
+use crate::bigint;
+use crate::bigint::AsLimbs;
+use crate::sqrt;
+use num::Integer;
+use std::fmt::Debug;
+use std::ops::{Add, Mul, Neg, Sub};
+
+pub trait FiniteField:
+    Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+    + Copy
+    + Debug
+    + PartialEq
+    + Sized
+{
+    /// The field modulus p (little endian u64 limbs)
+    const MODULUS: &'static [u64];
+
+    type ValueType: Ord + AsLimbs;
+
+    fn new(value: Self::ValueType) -> Self;
+
+    /// Init from any u64 (reduced mod p)
+    fn from_u64(value: u64) -> Self;
+
+    /// Init from an integer of any width (little endian u64 limbs, reduced mod p)
+    fn from_le_limbs(limbs: &[u64]) -> Self {
+        // Horner's method: ((l_k * 2^64 + l_(k-1)) * 2^64 + ...) + l_0
+        let two_pow_64 = Self::from_u64(u64::MAX) + Self::one();
+        limbs.iter().rev().fold(Self::min(), |acc, limb| {
+            acc * two_pow_64 + Self::from_u64(*limb)
+        })
+    }
+
+    fn min() -> Self;
+    fn max() -> Self;
+
+    fn one() -> Self;
+
+    fn value(&self) -> Self::ValueType;
+
+    /// self * self (field implementations can provide a faster version)
+    fn square(&self) -> Self {
+        *self * *self
+    }
+
+    /// Compute self^exp using square and multiply (exp: little endian u64 limbs)
+    fn pow(&self, exp: &[u64]) -> Self {
+        let mut res = Self::one();
+        let mut base = *self;
+
+        for limb in exp {
+            let mut limb = *limb;
+            for _ in 0..64 {
+                if limb & 1 == 1 {
+                    res = res * base;
+                }
+                base = base.square();
+                limb >>= 1;
+            }
+        }
+
+        res
+    }
+
+    /// Multiplicative inverse using Fermat's little theorem: n^(p - 1) = 1 => n^(p - 2) = 1/n
+    /// Return None for 0 (0 has no inverse)
+    fn inverse_fermat(&self) -> Option<Self> {
+        if *self == Self::min() {
+            None
+        } else {
+            Some(self.pow(&bigint::sub_word(Self::MODULUS, 2)))
+        }
+    }
+
+    /// Multiplicative inverse: n * mul_inv(n) = 1
+    /// Return None for 0 (0 has no inverse)
+    fn mul_inverse(&self) -> Option<Self> {
+        self.inverse_fermat()
+    }
+
+    /// Size in bytes of the (big endian) encoding of a field element
+    fn byte_len() -> usize {
+        (bigint::bits(Self::MODULUS) as usize).div_ceil(8)
+    }
+
+    /// Encode as big endian bytes (canonical, `byte_len()` bytes)
+    fn to_be_bytes(&self) -> Vec<u8> {
+        let value = self.value();
+        let mut bytes: Vec<u8> = value
+            .as_limbs()
+            .iter()
+            .flat_map(|l| l.to_le_bytes())
+            .collect();
+        bytes.resize(Self::byte_len(), 0);
+        bytes.reverse();
+        bytes
+    }
+
+    /// Decode big endian bytes
+    /// Return None if the length is not `byte_len()` or if the value is not lower than p
+    fn from_be_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::byte_len() {
+            return None;
+        }
+
+        let limbs: Vec<u64> = bytes
+            .rchunks(8)
+            .map(|chunk| {
+                let mut buf = [0; 8];
+                buf[8 - chunk.len()..].copy_from_slice(chunk);
+                u64::from_be_bytes(buf)
+            })
+            .collect();
+        // Values >= p are reduced by from_le_limbs: the encoding would then differ
+        let res = Self::from_le_limbs(&limbs);
+        (res.to_be_bytes() == bytes).then_some(res)
+    }
+
+    /// Return true if the (canonical) value is odd
+    fn is_odd(&self) -> bool {
+        self.value().as_limbs()[0] & 1 == 1
+    }
+
+    /// Return true if n is a square (using Euler's criterion)
+    fn is_square(&self) -> bool {
+        sqrt::is_square(self)
+    }
+
+    /// Square roots: sqrt(n) * sqrt(n) = n
+    /// Return both roots (lowest value first) or None if n is not a square
+    fn square_root(&self) -> Option<(Self, Self)> {
+        sqrt::tonelli_shanks(self)
+    }
+}
+
+/// Implement `&T op &T`, `T op &T` and `&T op T` from `T op T` (T being Copy)
+macro_rules! forward_ref_binop {
+    ([$($gen:tt)*] $imp:ident, $method:ident for $t:ty) => {
+        impl<$($gen)*> $imp<&$t> for &$t {
+            type Output = $t;
+
+            fn $method(self, other: &$t) -> $t {
+                $imp::$method(*self, *other)
+            }
+        }
+
+        impl<$($gen)*> $imp<&$t> for $t {
+            type Output = $t;
+
+            fn $method(self, other: &$t) -> $t {
+                $imp::$method(self, *other)
+            }
+        }
+
+        impl<$($gen)*> $imp<$t> for &$t {
+            type Output = $t;
+
+            fn $method(self, other: $t) -> $t {
+                $imp::$method(*self, other)
+            }
+        }
+    };
+}
+
+/// Implement `-&T` from `-T` (T being Copy)
+macro_rules! forward_ref_unop {
+    ([$($gen:tt)*] $imp:ident, $method:ident for $t:ty) => {
+        impl<$($gen)*> $imp for &$t {
+            type Output = $t;
+
+            fn $method(self) -> $t {
+                $imp::$method(*self)
+            }
+        }
+    };
+}
+
+pub(crate) use forward_ref_binop;
+pub(crate) use forward_ref_unop;
+
+/// Prime finite field 𝔽p: the list of numbers 0 through P - 1
+/// Note: P must be a prime number (this is not checked)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fp<const P: u64> {
+    value: u64,
+}
+
+impl<const P: u64> Fp<P> {
+    pub const P: u64 = P;
+
+    pub fn try_new(value: u64) -> Result<Self, &'static str> {
+        if value < P {
+            Ok(Self { value })
+        } else {
+            Err("Value must be lower than P")
+        }
+    }
+
+    /// Multiplicative inverse using the extended Euclidean algorithm
+    /// Return None for 0 (0 has no inverse)
+    pub fn inverse_euclid(&self) -> Option<Self> {
+        if self.value == 0 {
+            return None;
+        }
+
+        // Find x & y such that: value * x + P * y = gcd(value, P) = 1 (as P is prime)
+        // so value * x = 1 (mod P)
+        let egcd = i128::from(self.value).extended_gcd(&i128::from(P));
+        debug_assert_eq!(egcd.gcd, 1);
+
+        Some(Self {
+            // Safe to unwrap as the result is modulo P
+            value: u64::try_from(egcd.x.mod_floor(&i128::from(P))).unwrap(),
+        })
+    }
+}
+
+impl<const P: u64> FiniteField for Fp<P> {
+    const MODULUS: &'static [u64] = &[P];
+
+    type ValueType = u64;
+
+    fn new(value: Self::ValueType) -> Self {
+        Self::try_new(value)
+            .unwrap_or_else(|_| panic!("Cannot init a Fp<{P}> point with value higher than P - 1"))
+    }
+
+    fn from_u64(value: u64) -> Self {
+        Self { value: value % P }
+    }
+
+    fn min() -> Self {
+        Self { value: 0 }
+    }
+
+    fn max() -> Self {
+        Self { value: P - 1 }
+    }
+
+    fn one() -> Self {
+        Self { value: 1 }
+    }
+
+    fn value(&self) -> Self::ValueType {
+        self.value
+    }
+
+    fn mul_inverse(&self) -> Option<Self> {
+        self.inverse_euclid()
+    }
+}
+
+impl<const P: u64> Add for Fp<P> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        // Need to use u128 here as P - 1 + P - 1 can overflow u64
+        let v = (u128::from(self.value) + u128::from(other.value)).mod_floor(&u128::from(P));
+
+        Self {
+            // Safe to unwrap as the result of the addition is modulo P
+            value: u64::try_from(v).unwrap(),
+        }
+    }
+}
+
+impl<const P: u64> Sub for Fp<P> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        // Both values are in [0; P - 1] so no need for signed integers here
+        let value = if self.value >= other.value {
+            self.value - other.value
+        } else {
+            P - (other.value - self.value)
+        };
+
+        Self { value }
+    }
+}
+
+impl<const P: u64> Mul for Fp<P> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        // Need to use u128 here as (P - 1) * (P - 1) can overflow u64
+        let v = (u128::from(self.value) * u128::from(other.value)).mod_floor(&u128::from(P));
+
+        Self {
+            // Safe to unwrap as the result of the multiplication is modulo P
+            value: u64::try_from(v).unwrap(),
+        }
+    }
+}
+
+impl<const P: u64> Neg for Fp<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        // Definition for: -n
+        // n + (-n) = 0
+        Self::min() - self
+    }
+}
+
+forward_ref_binop!([const P: u64] Add, add for Fp<P>);
+forward_ref_binop!([const P: u64] Sub, sub for Fp<P>);
+forward_ref_binop!([const P: u64] Mul, mul for Fp<P>);
+forward_ref_unop!([const P: u64] Neg, neg for Fp<P>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A "larger" prime: 2^31 - 1
+    type F31 = Fp<2147483647>;
+    // Largest prime below 2^64
+    type F64 = Fp<18446744073709551557>;
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(Fp::<7>::try_new(6), Ok(Fp::<7>::new(6)));
+        assert!(Fp::<7>::try_new(7).is_err());
+    }
+
+    #[test]
+    fn test_small_fields() {
+        for v in 0..Fp::<2>::P {
+            let a = Fp::<2>::new(v);
+            assert_eq!(a + -a, Fp::<2>::min());
+        }
+        for v in 1..Fp::<13>::P {
+            let a = Fp::<13>::new(v);
+            assert_eq!(a * a.mul_inverse().unwrap(), Fp::<13>::one());
+        }
+        assert_eq!(Fp::<13>::from_u64(27), Fp::<13>::new(1));
+        assert_eq!(Fp::<13>::from_u64(u64::MAX), Fp::<13>::new(u64::MAX % 13));
+        // (2^128 + 2^64 * 5 + 7) % 73
+        assert_eq!(Fp::<73>::from_le_limbs(&[7, 5, 1]), Fp::<73>::new(21));
+        assert_eq!(Fp::<73>::from_le_limbs(&[]), Fp::<73>::min());
+    }
+
+    #[test]
+    fn test_no_overflow() {
+        assert_eq!(F31::max() + F31::max(), F31::new(F31::P - 2));
+        assert_eq!(F31::max() * F31::max(), F31::new(1));
+        assert_eq!(F64::max() + F64::max(), F64::new(F64::P - 2));
+        assert_eq!(F64::max() * F64::max(), F64::new(1));
+        assert_eq!(F64::min() - F64::max(), F64::new(1));
+        assert_eq!(-F64::max(), F64::new(1));
+    }
+
+    #[test]
+    fn test_bytes() {
+        type F = Fp<257>;
+        assert_eq!(F::byte_len(), 2);
+        assert_eq!(Fp::<61>::byte_len(), 1);
+
+        assert_eq!(F::new(256).to_be_bytes(), vec![1, 0]);
+        assert_eq!(F::new(3).to_be_bytes(), vec![0, 3]);
+        assert_eq!(F::from_be_bytes(&[1, 0]), Some(F::new(256)));
+        assert!(F::new(255).is_odd());
+        assert!(!F::new(256).is_odd());
+
+        // Wrong length or value >= p
+        assert_eq!(F::from_be_bytes(&[3]), None);
+        assert_eq!(F::from_be_bytes(&[0, 0, 3]), None);
+        assert_eq!(F::from_be_bytes(&[1, 1]), None);
+    }
+
+    #[test]
+    fn test_pow() {
+        assert_eq!(F31::new(2).pow(&[0]), F31::one());
+        assert_eq!(F31::new(2).pow(&[30]), F31::new(1 << 30));
+        assert_eq!(F31::new(2).pow(&[31]), F31::one());
+        assert_eq!(F64::min().pow(&[5]), F64::min());
+    }
+
+    #[test]
+    fn test_mul_inverse() {
+        assert_eq!(F31::min().inverse_euclid(), None);
+        assert_eq!(F31::min().inverse_fermat(), None);
+        assert_eq!(F64::min().mul_inverse(), None);
+
+        for v in [1, 2, 3, 12345, F64::P - 2, F64::P - 1] {
+            let a = F64::new(v);
+            let inv = a.inverse_euclid().unwrap();
+            assert_eq!(a * inv, F64::one());
+            assert_eq!(a.inverse_fermat(), Some(inv));
+        }
+
+        for v in 1..Fp::<101>::P {
+            let a = Fp::<101>::new(v);
+            assert_eq!(a.inverse_euclid(), a.inverse_fermat());
+        }
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_ref_ops() {
+        let a = F31::new(123456);
+        let b = F31::new(654321);
+        assert_eq!(&a + &b, a + b);
+        assert_eq!(a + &b, a + b);
+        assert_eq!(&a - b, a - b);
+        assert_eq!(&a * &b, a * b);
+        assert_eq!(-&a, -a);
+    }
+}
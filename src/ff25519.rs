@@ -0,0 +1,512 @@
+//! Prime finite field 𝔽p for p = 2^255 - 19 (the Curve25519 base field)
+//!
+//! Values are stored using 5 limbs of 51 bits (radix 2^51): v = l0 + l1 * 2^51 + ... + l4 * 2^204
+//! Since 2^255 = 19 (mod p), the part of a product above 2^255 can be folded back
+//! by multiplying it by 19 (instead of a division by p).
+//! Limbs are allowed to be (a bit) larger than 2^51 between operations: carries are only
+//! propagated once per operation (lazy carry) and the value is only fully reduced
+//! when needed (comparison, encoding).
+//!
+//! From: https://cr.yp.to/ecdh/curve25519-20060209.pdf (section 4)
+
+use crate::bigint::Uint;
+use crate::ff::{forward_ref_binop, forward_ref_unop, FiniteField};
+use crate::sqrt;
+use std::fmt;
+use std::fmt::Debug;
+use std::ops::{Add, Mul, Neg, Sub};
+
+const LOW_51_BIT_MASK: u64 = (1 << 51) - 1;
+
+/// Finite field 𝔽p with p = 2^255 - 19
+#[derive(Clone, Copy)]
+pub struct FiniteField25519Point {
+    limbs: [u64; 5],
+}
+
+impl FiniteField25519Point {
+    /// 2 * p in radix 2^51 (used for subtraction, so limbs never underflow)
+    const TWO_P: [u64; 5] = [
+        2 * ((1 << 51) - 19),
+        2 * LOW_51_BIT_MASK,
+        2 * LOW_51_BIT_MASK,
+        2 * LOW_51_BIT_MASK,
+        2 * LOW_51_BIT_MASK,
+    ];
+
+    /// sqrt(-1) = 2^((p - 1) / 4)
+    pub const SQRT_M1: Self = Self::from_limbs([
+        1718705420411056,
+        234908883556509,
+        2233514472574048,
+        2117202627021982,
+        765476049583133,
+    ]);
+
+    const fn from_limbs(limbs: [u64; 5]) -> Self {
+        Self { limbs }
+    }
+
+    /// Decode 32 little endian bytes, the highest bit is ignored (as in RFC 7748)
+    /// Note: non canonical values (in [p; 2^255 - 1]) are accepted
+    pub fn from_bytes(bytes: &[u8; 32]) -> Self {
+        let load = |i: usize| u64::from_le_bytes(bytes[i..i + 8].try_into().unwrap());
+
+        Self::from_limbs([
+            load(0) & LOW_51_BIT_MASK,
+            (load(6) >> 3) & LOW_51_BIT_MASK,
+            (load(12) >> 6) & LOW_51_BIT_MASK,
+            (load(19) >> 1) & LOW_51_BIT_MASK,
+            (load(24) >> 12) & LOW_51_BIT_MASK,
+        ])
+    }
+
+    /// Encode as 32 little endian bytes (canonical encoding)
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        bytes.copy_from_slice(&self.value().to_le_bytes());
+        bytes
+    }
+
+    /// Propagate the carries once: limbs are then < 2^51 + 2^13 (not fully reduced)
+    fn weak_reduce(mut limbs: [u64; 5]) -> Self {
+        let c0 = limbs[0] >> 51;
+        let c1 = limbs[1] >> 51;
+        let c2 = limbs[2] >> 51;
+        let c3 = limbs[3] >> 51;
+        let c4 = limbs[4] >> 51;
+
+        limbs[0] &= LOW_51_BIT_MASK;
+        limbs[1] &= LOW_51_BIT_MASK;
+        limbs[2] &= LOW_51_BIT_MASK;
+        limbs[3] &= LOW_51_BIT_MASK;
+        limbs[4] &= LOW_51_BIT_MASK;
+
+        // c4 is the part above 2^255: 2^255 = 19 (mod p)
+        limbs[0] += c4 * 19;
+        limbs[1] += c0;
+        limbs[2] += c1;
+        limbs[3] += c2;
+        limbs[4] += c3;
+
+        Self::from_limbs(limbs)
+    }
+
+    /// Fully reduce: return the canonical limbs (value in [0; p - 1], limbs < 2^51)
+    fn canonical_limbs(&self) -> [u64; 5] {
+        let mut limbs = Self::weak_reduce(self.limbs).limbs;
+
+        // value < 2p here: compute q = 1 if value >= p (i.e. value + 19 >= 2^255) else 0
+        let mut q = (limbs[0] + 19) >> 51;
+        q = (limbs[1] + q) >> 51;
+        q = (limbs[2] + q) >> 51;
+        q = (limbs[3] + q) >> 51;
+        q = (limbs[4] + q) >> 51;
+
+        // value - q * p = value + 19 * q - q * 2^255
+        limbs[0] += 19 * q;
+        limbs[1] += limbs[0] >> 51;
+        limbs[0] &= LOW_51_BIT_MASK;
+        limbs[2] += limbs[1] >> 51;
+        limbs[1] &= LOW_51_BIT_MASK;
+        limbs[3] += limbs[2] >> 51;
+        limbs[2] &= LOW_51_BIT_MASK;
+        limbs[4] += limbs[3] >> 51;
+        limbs[3] &= LOW_51_BIT_MASK;
+        // Drop q * 2^255
+        limbs[4] &= LOW_51_BIT_MASK;
+
+        limbs
+    }
+
+    /// self^2 (cheaper than self * self)
+    fn square_limbs(&self) -> Self {
+        let a = &self.limbs;
+        let m = |x: u64, y: u64| u128::from(x) * u128::from(y);
+
+        let a3_19 = 19 * a[3];
+        let a4_19 = 19 * a[4];
+
+        let r0 = m(a[0], a[0]) + 2 * (m(a[1], a4_19) + m(a[2], a3_19));
+        let r1 = m(a[3], a3_19) + 2 * (m(a[0], a[1]) + m(a[2], a4_19));
+        let r2 = m(a[1], a[1]) + 2 * (m(a[0], a[2]) + m(a[4], a3_19));
+        let r3 = m(a[4], a4_19) + 2 * (m(a[0], a[3]) + m(a[1], a[2]));
+        let r4 = m(a[2], a[2]) + 2 * (m(a[0], a[4]) + m(a[1], a[3]));
+
+        Self::carry_wide([r0, r1, r2, r3, r4])
+    }
+
+    /// Carry propagation from 128 bits accumulators to 51 bits limbs
+    fn carry_wide(r: [u128; 5]) -> Self {
+        let mut r = r;
+        r[1] += r[0] >> 51;
+        r[2] += r[1] >> 51;
+        r[3] += r[2] >> 51;
+        r[4] += r[3] >> 51;
+        let c4 = (r[4] >> 51) as u64;
+
+        let mut limbs = [
+            (r[0] as u64) & LOW_51_BIT_MASK,
+            (r[1] as u64) & LOW_51_BIT_MASK,
+            (r[2] as u64) & LOW_51_BIT_MASK,
+            (r[3] as u64) & LOW_51_BIT_MASK,
+            (r[4] as u64) & LOW_51_BIT_MASK,
+        ];
+        // 2^255 = 19 (mod p)
+        limbs[0] += c4 * 19;
+        limbs[1] += limbs[0] >> 51;
+        limbs[0] &= LOW_51_BIT_MASK;
+
+        Self::from_limbs(limbs)
+    }
+
+    /// self^(2^k)
+    fn pow2k(&self, k: u32) -> Self {
+        let mut res = *self;
+        for _ in 0..k {
+            res = res.square_limbs();
+        }
+        res
+    }
+
+    /// Return (self^(2^250 - 1), self^11), shared by invert & sqrt
+    /// From: https://cr.yp.to/ecdh/curve25519-20060209.pdf (addition chain)
+    fn pow22501(&self) -> (Self, Self) {
+        let t0 = self.square_limbs(); // 2
+        let t1 = t0.pow2k(2); // 8
+        let t2 = *self * t1; // 9
+        let t3 = t0 * t2; // 11
+        let t4 = t3.square_limbs(); // 22
+        let t5 = t2 * t4; // 2^5 - 1
+        let t6 = t5.pow2k(5);
+        let t7 = t6 * t5; // 2^10 - 1
+        let t8 = t7.pow2k(10);
+        let t9 = t8 * t7; // 2^20 - 1
+        let t10 = t9.pow2k(20);
+        let t11 = t10 * t9; // 2^40 - 1
+        let t12 = t11.pow2k(10);
+        let t13 = t12 * t7; // 2^50 - 1
+        let t14 = t13.pow2k(50);
+        let t15 = t14 * t13; // 2^100 - 1
+        let t16 = t15.pow2k(100);
+        let t17 = t16 * t15; // 2^200 - 1
+        let t18 = t17.pow2k(50);
+        let t19 = t18 * t13; // 2^250 - 1
+
+        (t19, t3)
+    }
+
+    /// self^((p - 5) / 8) = self^(2^252 - 3)
+    fn pow_p58(&self) -> Self {
+        let (t19, _) = self.pow22501();
+        // 2^252 - 4 + 1
+        t19.pow2k(2) * *self
+    }
+}
+
+impl FiniteField for FiniteField25519Point {
+    const MODULUS: &'static [u64] = &[
+        0xffffffffffffffed,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x7fffffffffffffff,
+    ];
+
+    type ValueType = Uint<4>;
+
+    fn new(value: Self::ValueType) -> Self {
+        if value
+            >= Uint::from_limbs([
+                Self::MODULUS[0],
+                Self::MODULUS[1],
+                Self::MODULUS[2],
+                Self::MODULUS[3],
+            ])
+        {
+            panic!("Cannot init a FiniteField25519Point with value higher than P - 1");
+        }
+
+        let mut bytes = [0; 32];
+        bytes.copy_from_slice(&value.to_le_bytes());
+        Self::from_bytes(&bytes)
+    }
+
+    fn from_u64(value: u64) -> Self {
+        Self::from_limbs([value & LOW_51_BIT_MASK, value >> 51, 0, 0, 0])
+    }
+
+    fn min() -> Self {
+        Self::from_limbs([0; 5])
+    }
+
+    fn max() -> Self {
+        Self::from_limbs([
+            (1 << 51) - 20,
+            LOW_51_BIT_MASK,
+            LOW_51_BIT_MASK,
+            LOW_51_BIT_MASK,
+            LOW_51_BIT_MASK,
+        ])
+    }
+
+    fn one() -> Self {
+        Self::from_limbs([1, 0, 0, 0, 0])
+    }
+
+    fn value(&self) -> Self::ValueType {
+        let l = self.canonical_limbs();
+        Uint::from_limbs([
+            l[0] | (l[1] << 51),
+            (l[1] >> 13) | (l[2] << 38),
+            (l[2] >> 26) | (l[3] << 25),
+            (l[3] >> 39) | (l[4] << 12),
+        ])
+    }
+
+    fn square(&self) -> Self {
+        self.square_limbs()
+    }
+
+    fn mul_inverse(&self) -> Option<Self> {
+        if *self == Self::min() {
+            return None;
+        }
+
+        // self^(p - 2) = self^(2^255 - 21) = (self^(2^250 - 1))^(2^5) * self^11
+        let (t19, t3) = self.pow22501();
+        Some(t19.pow2k(5) * t3)
+    }
+
+    fn square_root(&self) -> Option<(Self, Self)> {
+        // p = 5 mod 8: r = self^((p + 3) / 8) = self * self^((p - 5) / 8)
+        // if r^2 == -self then r * sqrt(-1) is a square root
+        let r = *self * self.pow_p58();
+        let r_square = r.square_limbs();
+
+        if r_square == *self {
+            Some(sqrt::canonical_roots(r))
+        } else if r_square == -*self {
+            Some(sqrt::canonical_roots(r * Self::SQRT_M1))
+        } else {
+            None
+        }
+    }
+}
+
+impl PartialEq for FiniteField25519Point {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical_limbs() == other.canonical_limbs()
+    }
+}
+
+impl Eq for FiniteField25519Point {}
+
+impl Debug for FiniteField25519Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FiniteField25519Point({:?})", self.value())
+    }
+}
+
+impl Add for FiniteField25519Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let mut limbs = self.limbs;
+        for (l, o) in limbs.iter_mut().zip(other.limbs) {
+            *l += o;
+        }
+        Self::weak_reduce(limbs)
+    }
+}
+
+impl Sub for FiniteField25519Point {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        // self + 2p - other: limbs cannot underflow as other limbs are < 2^51 + 2^13
+        let mut limbs = self.limbs;
+        for ((l, two_p), o) in limbs.iter_mut().zip(Self::TWO_P).zip(other.limbs) {
+            *l = *l + two_p - o;
+        }
+        Self::weak_reduce(limbs)
+    }
+}
+
+impl Mul for FiniteField25519Point {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let a = &self.limbs;
+        let b = &other.limbs;
+        let m = |x: u64, y: u64| u128::from(x) * u128::from(y);
+
+        // Limbs above 2^255 are folded back: b_i * 2^(51 * (i + 5)) = 19 * b_i * 2^(51 * i)
+        let b1_19 = 19 * b[1];
+        let b2_19 = 19 * b[2];
+        let b3_19 = 19 * b[3];
+        let b4_19 = 19 * b[4];
+
+        let r0 = m(a[0], b[0]) + m(a[1], b4_19) + m(a[2], b3_19) + m(a[3], b2_19) + m(a[4], b1_19);
+        let r1 = m(a[0], b[1]) + m(a[1], b[0]) + m(a[2], b4_19) + m(a[3], b3_19) + m(a[4], b2_19);
+        let r2 = m(a[0], b[2]) + m(a[1], b[1]) + m(a[2], b[0]) + m(a[3], b4_19) + m(a[4], b3_19);
+        let r3 = m(a[0], b[3]) + m(a[1], b[2]) + m(a[2], b[1]) + m(a[3], b[0]) + m(a[4], b4_19);
+        let r4 = m(a[0], b[4]) + m(a[1], b[3]) + m(a[2], b[2]) + m(a[3], b[1]) + m(a[4], b[0]);
+
+        Self::carry_wide([r0, r1, r2, r3, r4])
+    }
+}
+
+impl Neg for FiniteField25519Point {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::min() - self
+    }
+}
+
+forward_ref_binop!([] Add, add for FiniteField25519Point);
+forward_ref_binop!([] Sub, sub for FiniteField25519Point);
+forward_ref_binop!([] Mul, mul for FiniteField25519Point);
+forward_ref_unop!([] Neg, neg for FiniteField25519Point);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type F = FiniteField25519Point;
+
+    fn from_hex(hex: &str) -> F {
+        F::new(Uint::from_be_hex(hex))
+    }
+
+    #[test]
+    fn test_basic() {
+        assert_eq!(F::max() + F::one(), F::min());
+        assert_eq!(F::min() - F::one(), F::max());
+        assert_eq!(
+            F::max().value(),
+            Uint::from_limbs([
+                0xffffffffffffffec,
+                0xffffffffffffffff,
+                0xffffffffffffffff,
+                0x7fffffffffffffff,
+            ])
+        );
+        assert_eq!(F::from_u64(u64::MAX).value(), Uint::from_u64(u64::MAX));
+        assert_eq!(F::max() * F::max(), F::one());
+        assert_eq!(F::SQRT_M1 * F::SQRT_M1, -F::one());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_invalid() {
+        F::new(Uint::from_be_hex(
+            "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed",
+        ));
+    }
+
+    #[test]
+    fn test_bytes() {
+        let a = from_hex("1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e7f809");
+        let bytes = a.to_bytes();
+        assert_eq!(F::from_bytes(&bytes), a);
+        assert_eq!(bytes[0], 0x09);
+        assert_eq!(bytes[31], 0x1a);
+
+        // Highest bit is ignored
+        let mut bytes_hb = bytes;
+        bytes_hb[31] |= 0x80;
+        assert_eq!(F::from_bytes(&bytes_hb), a);
+
+        // Non canonical: p + 1 is decoded as 1 and encoded canonically
+        let mut p_plus_1 = [0xff; 32];
+        p_plus_1[0] = 0xee;
+        p_plus_1[31] = 0x7f;
+        assert_eq!(F::from_bytes(&p_plus_1), F::one());
+        assert_eq!(F::from_bytes(&p_plus_1).to_bytes(), F::one().to_bytes());
+    }
+
+    // Test vectors computed with python (p = 2**255 - 19)
+    const A: &str = "1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e7f80d";
+    const B: &str = "7ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff000";
+
+    #[test]
+    fn test_mul() {
+        let a = from_hex(A);
+        let b = from_hex(B);
+        assert_eq!(
+            a * b,
+            from_hex("3d70a3d70b3d4073a6da0d4073b6d70a3d70a3d70b3d4073a6da0d4073b65b11")
+        );
+        assert_eq!(
+            b * b,
+            from_hex("0000000000000000000000000000000000000000000000000000000000fda169")
+        );
+        assert_eq!(
+            a * F::from_u64(19),
+            from_hex("713579be0245599de2266aaef33768acf13579be0245599de2266aaef3376930")
+        );
+    }
+
+    #[test]
+    fn test_square() {
+        let a = from_hex(A);
+        let b = from_hex(B);
+        assert_eq!(
+            a.square(),
+            from_hex("606ea6f851e15ea1b99523533aa36b0f105de6996a21d865d9212ceb97fbcfbf")
+        );
+        assert_eq!(b.square(), b * b);
+        assert_eq!(F::max().square(), F::one());
+    }
+
+    #[test]
+    fn test_invert() {
+        let a = from_hex(A);
+        let b = from_hex(B);
+        assert_eq!(
+            a.mul_inverse(),
+            Some(from_hex(
+                "414d24d13762b1fefc7cb9f597f89eaee428fc47fbdda9b1b935b38283c59ed6"
+            ))
+        );
+        assert_eq!(
+            b.mul_inverse(),
+            Some(from_hex(
+                "11a4f3e19be924dbc4f9e8c4693cf866fa4936f13e7a311a4f3e19be924dbc4d"
+            ))
+        );
+        assert_eq!(a.mul_inverse(), a.inverse_fermat());
+        assert_eq!(F::min().mul_inverse(), None);
+    }
+
+    #[test]
+    fn test_sqrt() {
+        let a = from_hex(A);
+        let b = from_hex(B);
+        // a is a square & the root r needs the sqrt(-1) correction, b is not a square
+        assert_eq!(
+            a.square_root(),
+            Some((
+                from_hex("3606e94c2a9d3a19fde71339742384147593fb6cfb4b716d1707055817c046ca"),
+                from_hex("49f916b3d562c5e60218ecc68bdc7beb8a6c049304b48e92e8f8faa7e83fb923")
+            ))
+        );
+        assert_eq!(
+            F::from_u64(4).square_root(),
+            Some((F::from_u64(2), -F::from_u64(2)))
+        );
+        assert_eq!(b.square_root(), None);
+        assert_eq!(
+            (-F::one()).square_root(),
+            Some(sqrt::canonical_roots(F::SQRT_M1))
+        );
+        assert_eq!(F::min().square_root(), Some((F::min(), F::min())));
+
+        // Same result as the generic algorithms
+        for v in [a, b, F::from_u64(2), F::from_u64(3), F::max()] {
+            assert_eq!(v.square_root(), sqrt::tonelli_shanks(&v));
+            assert_eq!(v.is_square(), v.square_root().is_some());
+        }
+    }
+}
@@ -0,0 +1,379 @@
+//! Prime finite field 𝔽p for the Goldilocks prime p = 2^448 - 2^224 - 1 (the Curve448 base field)
+//!
+//! Values are stored using 8 limbs of 56 bits (radix 2^56): v = l0 + l1 * 2^56 + ... + l7 * 2^392
+//! Since 2^448 = 2^224 + 1 (mod p) and 224 = 4 * 56, the part of a product above 2^448
+//! is folded back by adding each limb twice: once 8 limbs lower and once 4 limbs lower.
+//! As for 𝔽(2^255 - 19), carries are propagated lazily and the value is only fully
+//! reduced when needed (comparison, encoding).
+//!
+//! From: https://eprint.iacr.org/2015/625.pdf (section 3)
+
+use crate::bigint::Uint;
+use crate::ff::{forward_ref_binop, forward_ref_unop, FiniteField};
+use std::fmt;
+use std::fmt::Debug;
+use std::ops::{Add, Mul, Neg, Sub};
+
+const LOW_56_BIT_MASK: u64 = (1 << 56) - 1;
+
+/// Finite field 𝔽p with p = 2^448 - 2^224 - 1
+#[derive(Clone, Copy)]
+pub struct FiniteField448Point {
+    limbs: [u64; 8],
+}
+
+impl FiniteField448Point {
+    /// 2 * p in radix 2^56 (used for subtraction, so limbs never underflow)
+    const TWO_P: [u64; 8] = [
+        2 * LOW_56_BIT_MASK,
+        2 * LOW_56_BIT_MASK,
+        2 * LOW_56_BIT_MASK,
+        2 * LOW_56_BIT_MASK,
+        2 * (LOW_56_BIT_MASK - 1),
+        2 * LOW_56_BIT_MASK,
+        2 * LOW_56_BIT_MASK,
+        2 * LOW_56_BIT_MASK,
+    ];
+
+    const fn from_limbs(limbs: [u64; 8]) -> Self {
+        Self { limbs }
+    }
+
+    /// Decode 56 little endian bytes (as in RFC 7748)
+    /// Note: non canonical values (in [p; 2^448 - 1]) are accepted
+    pub fn from_bytes(bytes: &[u8; 56]) -> Self {
+        let mut limbs = [0; 8];
+        for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(7)) {
+            let mut buf = [0; 8];
+            buf[..7].copy_from_slice(chunk);
+            *limb = u64::from_le_bytes(buf);
+        }
+        Self::from_limbs(limbs)
+    }
+
+    /// Encode as 56 little endian bytes (canonical encoding)
+    pub fn to_bytes(&self) -> [u8; 56] {
+        let mut bytes = [0; 56];
+        for (chunk, limb) in bytes.chunks_mut(7).zip(self.canonical_limbs()) {
+            chunk.copy_from_slice(&limb.to_le_bytes()[..7]);
+        }
+        bytes
+    }
+
+    /// Propagate the carries once: limbs are then < 2^56 + 2^8 (not fully reduced)
+    fn weak_reduce(mut limbs: [u64; 8]) -> Self {
+        let mut carries = [0; 8];
+        for (c, l) in carries.iter_mut().zip(limbs.iter_mut()) {
+            *c = *l >> 56;
+            *l &= LOW_56_BIT_MASK;
+        }
+
+        for i in 0..7 {
+            limbs[i + 1] += carries[i];
+        }
+        // c7 is the part above 2^448: 2^448 = 2^224 + 1 (mod p)
+        limbs[0] += carries[7];
+        limbs[4] += carries[7];
+
+        Self::from_limbs(limbs)
+    }
+
+    /// Fully reduce: return the canonical limbs (value in [0; p - 1], limbs < 2^56)
+    fn canonical_limbs(&self) -> [u64; 8] {
+        let mut limbs = self.limbs;
+
+        // Propagate the carries until the value is < 2^448
+        loop {
+            for i in 0..7 {
+                limbs[i + 1] += limbs[i] >> 56;
+                limbs[i] &= LOW_56_BIT_MASK;
+            }
+            let c7 = limbs[7] >> 56;
+            if c7 == 0 {
+                break;
+            }
+            limbs[7] &= LOW_56_BIT_MASK;
+            limbs[0] += c7;
+            limbs[4] += c7;
+        }
+
+        // value < 2^448 < 2p here: compute q = 1 if value >= p (i.e. value + 2^224 + 1 >= 2^448)
+        let mut q = 1;
+        for (i, l) in limbs.iter().enumerate() {
+            let two_pow_224 = u64::from(i == 4);
+            q = (l + two_pow_224 + q) >> 56;
+        }
+
+        // value - q * p = value + q * (2^224 + 1) - q * 2^448
+        limbs[0] += q;
+        limbs[4] += q;
+        for i in 0..7 {
+            limbs[i + 1] += limbs[i] >> 56;
+            limbs[i] &= LOW_56_BIT_MASK;
+        }
+        // Drop q * 2^448
+        limbs[7] &= LOW_56_BIT_MASK;
+
+        limbs
+    }
+
+    /// Carry propagation from 128 bits accumulators to 56 bits limbs
+    fn carry_wide(r: [u128; 8]) -> Self {
+        let mut r = r;
+        for i in 0..7 {
+            r[i + 1] += r[i] >> 56;
+        }
+        let c7 = (r[7] >> 56) as u64;
+
+        let mut limbs = r.map(|x| (x as u64) & LOW_56_BIT_MASK);
+        // 2^448 = 2^224 + 1 (mod p)
+        limbs[0] += c7;
+        limbs[4] += c7;
+        limbs[1] += limbs[0] >> 56;
+        limbs[0] &= LOW_56_BIT_MASK;
+        limbs[5] += limbs[4] >> 56;
+        limbs[4] &= LOW_56_BIT_MASK;
+
+        Self::from_limbs(limbs)
+    }
+}
+
+impl FiniteField for FiniteField448Point {
+    const MODULUS: &'static [u64] = &[
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xfffffffeffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ];
+
+    type ValueType = Uint<7>;
+
+    fn new(value: Self::ValueType) -> Self {
+        let mut modulus = [0; 7];
+        modulus.copy_from_slice(Self::MODULUS);
+        if value >= Uint::from_limbs(modulus) {
+            panic!("Cannot init a FiniteField448Point with value higher than P - 1");
+        }
+
+        let mut bytes = [0; 56];
+        bytes.copy_from_slice(&value.to_le_bytes());
+        Self::from_bytes(&bytes)
+    }
+
+    fn from_u64(value: u64) -> Self {
+        Self::from_limbs([value & LOW_56_BIT_MASK, value >> 56, 0, 0, 0, 0, 0, 0])
+    }
+
+    fn min() -> Self {
+        Self::from_limbs([0; 8])
+    }
+
+    fn max() -> Self {
+        Self::from_limbs([
+            LOW_56_BIT_MASK - 1,
+            LOW_56_BIT_MASK,
+            LOW_56_BIT_MASK,
+            LOW_56_BIT_MASK,
+            LOW_56_BIT_MASK - 1,
+            LOW_56_BIT_MASK,
+            LOW_56_BIT_MASK,
+            LOW_56_BIT_MASK,
+        ])
+    }
+
+    fn one() -> Self {
+        Self::from_limbs([1, 0, 0, 0, 0, 0, 0, 0])
+    }
+
+    fn value(&self) -> Self::ValueType {
+        // Safe to unwrap: 56 bytes always fit in 7 limbs
+        Uint::from_le_bytes(&self.to_bytes()).unwrap()
+    }
+}
+
+impl PartialEq for FiniteField448Point {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical_limbs() == other.canonical_limbs()
+    }
+}
+
+impl Eq for FiniteField448Point {}
+
+impl Debug for FiniteField448Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FiniteField448Point({:?})", self.value())
+    }
+}
+
+impl Add for FiniteField448Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let mut limbs = self.limbs;
+        for (l, o) in limbs.iter_mut().zip(other.limbs) {
+            *l += o;
+        }
+        Self::weak_reduce(limbs)
+    }
+}
+
+impl Sub for FiniteField448Point {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        // self + 2p - other: limbs cannot underflow as other limbs are < 2^56 + 2^8
+        let mut limbs = self.limbs;
+        for ((l, two_p), o) in limbs.iter_mut().zip(Self::TWO_P).zip(other.limbs) {
+            *l = *l + two_p - o;
+        }
+        Self::weak_reduce(limbs)
+    }
+}
+
+impl Mul for FiniteField448Point {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let m = |x: u64, y: u64| u128::from(x) * u128::from(y);
+
+        // Schoolbook product: 15 accumulators of (at most) 8 products of 2 limbs
+        let mut r = [0u128; 15];
+        for (i, a) in self.limbs.iter().enumerate() {
+            for (j, b) in other.limbs.iter().enumerate() {
+                r[i + j] += m(*a, *b);
+            }
+        }
+
+        // Fold back the limbs above 2^448 (from the top, so limbs 12 to 14 are folded twice):
+        // r_k * 2^(56 * k) = r_k * 2^(56 * (k - 8)) * (2^224 + 1) (mod p)
+        for k in (8..15).rev() {
+            r[k - 4] += r[k];
+            r[k - 8] += r[k];
+        }
+
+        let mut low = [0u128; 8];
+        low.copy_from_slice(&r[..8]);
+        Self::carry_wide(low)
+    }
+}
+
+impl Neg for FiniteField448Point {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::min() - self
+    }
+}
+
+forward_ref_binop!([] Add, add for FiniteField448Point);
+forward_ref_binop!([] Sub, sub for FiniteField448Point);
+forward_ref_binop!([] Mul, mul for FiniteField448Point);
+forward_ref_unop!([] Neg, neg for FiniteField448Point);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ff_big::{BigFp, PrimeModulus};
+
+    type F = FiniteField448Point;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct P448;
+    impl PrimeModulus<7> for P448 {
+        const P: Uint<7> = Uint::from_be_hex(
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        );
+    }
+
+    type B = BigFp<P448, 7>;
+
+    fn from_hex(hex: &str) -> F {
+        F::new(Uint::from_be_hex(hex))
+    }
+
+    #[test]
+    fn test_basic() {
+        assert_eq!(F::max() + F::one(), F::min());
+        assert_eq!(F::min() - F::one(), F::max());
+        assert_eq!(F::max().value(), B::max().value());
+        assert_eq!(F::from_u64(u64::MAX).value(), Uint::from_u64(u64::MAX));
+        assert_eq!(F::max() * F::max(), F::one());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_invalid() {
+        F::new(P448::P);
+    }
+
+    #[test]
+    fn test_bytes() {
+        let a = F::from_u64(0x0102_0304_0506_0708);
+        let bytes = a.to_bytes();
+        assert_eq!(bytes[..8], [8, 7, 6, 5, 4, 3, 2, 1]);
+        assert_eq!(F::from_bytes(&bytes), a);
+
+        // Non canonical: p + 1 = 2^448 - 2^224 is decoded as 1 and encoded canonically
+        let mut p_plus_1 = [0xff; 56];
+        p_plus_1[..28].fill(0);
+        assert_eq!(F::from_bytes(&p_plus_1), F::one());
+        assert_eq!(F::from_bytes(&p_plus_1).to_bytes(), F::one().to_bytes());
+
+        // 2^448 - 1 = 2^224 (mod p)
+        let two_pow_224 = F::new(Uint::from_be_hex(&format!("1{}", "0".repeat(56))));
+        assert_eq!(F::from_bytes(&[0xff; 56]), two_pow_224);
+    }
+
+    #[test]
+    fn test_mul() {
+        // Computed with python: (a * b) % p
+        let a = from_hex(
+            "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+        );
+        let b = from_hex(
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffe00000000000000000000000000000000000000000000000000000000",
+        );
+        assert_eq!(
+            a * b,
+            from_hex("76543210fedcba9876543210fedcba9876543210fedcba987654321088888887777777788888888777777778888888877777777888888887")
+        );
+    }
+
+    #[test]
+    fn test_against_big() {
+        // Compare with the plain (long division) implementation
+        let mut a = F::from_u64(0x1234_5678_9abc_def0);
+        let mut b = F::max();
+        for _ in 0..100 {
+            let ba = B::new(a.value());
+            let bb = B::new(b.value());
+            assert_eq!((a + b).value(), (ba + bb).value());
+            assert_eq!((a - b).value(), (ba - bb).value());
+            assert_eq!((a * b).value(), (ba * bb).value());
+            assert_eq!((-a).value(), (-ba).value());
+
+            a = a * b + F::one();
+            b = b * b - a;
+        }
+
+        assert_eq!(
+            a.mul_inverse().unwrap().value(),
+            B::new(a.value()).mul_inverse().unwrap().value()
+        );
+        assert_eq!(F::min().mul_inverse(), None);
+    }
+
+    #[test]
+    fn test_square_root() {
+        // p = 3 mod 4: -1 is not a square
+        assert!(!(-F::one()).is_square());
+
+        let a = F::from_u64(0xdead_beef) * F::max();
+        let (r1, r2) = (a * a).square_root().unwrap();
+        assert!(r1 == a || r2 == a);
+        assert_eq!(r1, -r2);
+    }
+}
@@ -0,0 +1,311 @@
+use crate::bigint::Uint;
+use crate::ff::{forward_ref_binop, forward_ref_unop, FiniteField};
+use std::fmt;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A prime modulus made of N u64 limbs
+pub trait PrimeModulus<const N: usize>: Clone + Copy + Debug + PartialEq + Eq + 'static {
+    const P: Uint<N>;
+
+    /// Reduce a product (high * 2^(64 * N) + low) mod P
+    /// (a modulus with a special form can provide a faster version, see `nist.rs`)
+    fn reduce_wide(low: &Uint<N>, high: &Uint<N>) -> Uint<N> {
+        Uint::rem_wide(low, high, &Self::P)
+    }
+}
+
+/// Prime finite field 𝔽p for a (large) prime p made of N u64 limbs
+///
+/// Values are stored as integers in [0; p - 1] and are reduced after each multiplication
+/// using a plain long division (see `Uint::rem_wide`) unless the modulus provides
+/// a specialized reduction (`PrimeModulus::reduce_wide`)
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct BigFp<M: PrimeModulus<N>, const N: usize> {
+    value: Uint<N>,
+    modulus: PhantomData<M>,
+}
+
+impl<M: PrimeModulus<N>, const N: usize> BigFp<M, N> {
+    pub fn try_new(value: Uint<N>) -> Result<Self, &'static str> {
+        if value < M::P {
+            Ok(Self::from_uint_unchecked(value))
+        } else {
+            Err("Value must be lower than P")
+        }
+    }
+
+    const fn from_uint_unchecked(value: Uint<N>) -> Self {
+        Self {
+            value,
+            modulus: PhantomData,
+        }
+    }
+
+    /// Multiplication using the Karatsuba algorithm (instead of the schoolbook one)
+    pub fn mul_karatsuba(&self, other: &Self) -> Self {
+        let (low, high) = self.value.mul_karatsuba(&other.value);
+        Self::from_uint_unchecked(M::reduce_wide(&low, &high))
+    }
+}
+
+impl<M: PrimeModulus<N>, const N: usize> FiniteField for BigFp<M, N> {
+    const MODULUS: &'static [u64] = M::P.limbs();
+
+    type ValueType = Uint<N>;
+
+    fn new(value: Self::ValueType) -> Self {
+        Self::try_new(value)
+            .unwrap_or_else(|_| panic!("Cannot init a BigFp point with value higher than P - 1"))
+    }
+
+    fn from_u64(value: u64) -> Self {
+        Self::from_uint_unchecked(Uint::from_u64(value).rem(&M::P))
+    }
+
+    fn min() -> Self {
+        Self::from_uint_unchecked(Uint::ZERO)
+    }
+
+    fn max() -> Self {
+        Self::from_uint_unchecked(M::P.overflowing_sub(&Uint::ONE).0)
+    }
+
+    fn one() -> Self {
+        Self::from_uint_unchecked(Uint::ONE)
+    }
+
+    fn value(&self) -> Self::ValueType {
+        self.value
+    }
+}
+
+impl<M: PrimeModulus<N>, const N: usize> Debug for BigFp<M, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BigFp({:?})", self.value)
+    }
+}
+
+impl<M: PrimeModulus<N>, const N: usize> Add for BigFp<M, N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        // Both values are < P so the sum is < 2P: subtract P once if needed
+        let (sum, carry) = self.value.overflowing_add(&other.value);
+        let value = if carry || sum >= M::P {
+            sum.overflowing_sub(&M::P).0
+        } else {
+            sum
+        };
+
+        Self::from_uint_unchecked(value)
+    }
+}
+
+impl<M: PrimeModulus<N>, const N: usize> Sub for BigFp<M, N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        // Add P back on borrow (the wrapping add gives the right result)
+        let (diff, borrow) = self.value.overflowing_sub(&other.value);
+        let value = if borrow {
+            diff.overflowing_add(&M::P).0
+        } else {
+            diff
+        };
+
+        Self::from_uint_unchecked(value)
+    }
+}
+
+impl<M: PrimeModulus<N>, const N: usize> Mul for BigFp<M, N> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let (low, high) = self.value.mul_schoolbook(&other.value);
+        Self::from_uint_unchecked(M::reduce_wide(&low, &high))
+    }
+}
+
+impl<M: PrimeModulus<N>, const N: usize> Neg for BigFp<M, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::min() - self
+    }
+}
+
+forward_ref_binop!([M: PrimeModulus<N>, const N: usize] Add, add for BigFp<M, N>);
+forward_ref_binop!([M: PrimeModulus<N>, const N: usize] Sub, sub for BigFp<M, N>);
+forward_ref_binop!([M: PrimeModulus<N>, const N: usize] Mul, mul for BigFp<M, N>);
+forward_ref_unop!([M: PrimeModulus<N>, const N: usize] Neg, neg for BigFp<M, N>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ff::Fp;
+    use crate::sqrt;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct P61;
+    impl PrimeModulus<1> for P61 {
+        const P: Uint<1> = Uint::from_u64(61);
+    }
+
+    /// 2^255 - 19
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct P25519;
+    impl PrimeModulus<4> for P25519 {
+        const P: Uint<4> =
+            Uint::from_be_hex("7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed");
+    }
+
+    /// NIST P-256 prime: 2^256 - 2^224 + 2^192 + 2^96 - 1
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct P256;
+    impl PrimeModulus<4> for P256 {
+        const P: Uint<4> =
+            Uint::from_be_hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+    }
+
+    /// BLS12-381 base field prime (381 bits)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct P381;
+    impl PrimeModulus<6> for P381 {
+        const P: Uint<6> = Uint::from_be_hex(
+            "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab",
+        );
+    }
+
+    /// BLS12-381 scalar field prime (p = 1 mod 2^32)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct R381;
+    impl PrimeModulus<4> for R381 {
+        const P: Uint<4> =
+            Uint::from_be_hex("73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001");
+    }
+
+    type F61 = BigFp<P61, 1>;
+    type F25519 = BigFp<P25519, 4>;
+    type F256 = BigFp<P256, 4>;
+    type F381 = BigFp<P381, 6>;
+    type FR381 = BigFp<R381, 4>;
+
+    /// Some (deterministic) pseudo random values
+    fn values<F: FiniteField>(seed: u64, count: usize) -> Vec<F> {
+        let mut v = F::one();
+        let mut step = F::one();
+        for _ in 0..seed {
+            step = step + F::one();
+        }
+        (0..count)
+            .map(|_| {
+                v = v * step + F::max();
+                step = step * step + F::one();
+                v
+            })
+            .collect()
+    }
+
+    fn check_field<F: FiniteField>() {
+        let vs = values::<F>(7, 8);
+        for a in &vs {
+            assert_eq!(*a + -*a, F::min());
+            assert_eq!(*a * F::one(), *a);
+            let inv = a.mul_inverse().unwrap();
+            assert_eq!(*a * inv, F::one());
+            let sq = *a * *a;
+            let (r1, r2) = sq.square_root().unwrap();
+            assert!(r1 == *a || r2 == *a);
+            for b in &vs {
+                assert_eq!(*a + *b, *b + *a);
+                assert_eq!(*a * *b, *b * *a);
+                assert_eq!((*a + *b) * *a, *a * *a + *b * *a);
+                assert_eq!(*a - *b, -(*b - *a));
+            }
+        }
+        assert_eq!(F::max() + F::one(), F::min());
+        assert_eq!(F::max() * F::max(), F::one());
+        assert_eq!(F::min().mul_inverse(), None);
+    }
+
+    #[test]
+    fn test_against_fp() {
+        // Exhaustive check against Fp<61>
+        for a in 0..61 {
+            let fa = F61::from_u64(a);
+            let pa = Fp::<61>::new(a);
+            assert_eq!(fa.value().limbs()[0], pa.value());
+            assert_eq!((-fa).value().limbs()[0], (-pa).value());
+            assert_eq!(
+                fa.square_root().map(|r| r.0.value().limbs()[0]),
+                pa.square_root().map(|r| r.0.value())
+            );
+            for b in 0..61 {
+                let fb = F61::from_u64(b);
+                let pb = Fp::<61>::new(b);
+                assert_eq!((fa + fb).value().limbs()[0], (pa + pb).value());
+                assert_eq!((fa - fb).value().limbs()[0], (pa - pb).value());
+                assert_eq!((fa * fb).value().limbs()[0], (pa * pb).value());
+            }
+        }
+    }
+
+    #[test]
+    fn test_fields() {
+        check_field::<F25519>();
+        check_field::<F256>();
+        check_field::<F381>();
+        check_field::<FR381>();
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert!(F256::try_new(P256::P).is_err());
+        assert!(F256::try_new(Uint::MAX).is_err());
+        assert_eq!(F256::try_new(Uint::from_u64(5)), Ok(F256::from_u64(5)));
+    }
+
+    #[test]
+    fn test_mul_vectors() {
+        // Computed with python: (a * b) % p
+        let a = F256::new(Uint::from_be_hex(
+            "c0ffee0123456789abcdef0123456789abcdef0123456789abcdef0123456789",
+        ));
+        let b = F256::new(Uint::from_be_hex(
+            "deadbeef00000000fffffffffffffffff0000000000000001111111111111111",
+        ));
+        let expected = F256::new(Uint::from_be_hex(
+            "cdec7ce3568a5d26d6dc919204eeee2376630d890fff14e4a2768908950ecb66",
+        ));
+        assert_eq!(a * b, expected);
+        assert_eq!(a.mul_karatsuba(&b), expected);
+
+        let a = F381::new(Uint::from_be_hex(
+            "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+        ));
+        let expected = F381::new(Uint::from_be_hex(
+            "18155a607a381f89fb636e0df23ab767849faa6965b9dfbc67e364a458ff4d46878fe1012ef3c1dfb0e3ee0328317f79",
+        ));
+        assert_eq!(a * a, expected);
+        assert_eq!(a.mul_karatsuba(&a), expected);
+    }
+
+    #[test]
+    fn test_sqrt_paths() {
+        // 2^255 - 19 = 5 mod 8 (Atkin), P-256 = 3 mod 4, R381 = 1 mod 2^32 (Tonelli-Shanks)
+        for v in values::<F25519>(3, 10) {
+            assert_eq!(sqrt::tonelli_shanks(&v), sqrt::cipolla(&v));
+        }
+        for v in values::<F256>(3, 10) {
+            assert_eq!(sqrt::tonelli_shanks(&v), sqrt::cipolla(&v));
+        }
+        for v in values::<FR381>(3, 10) {
+            assert_eq!(sqrt::tonelli_shanks(&v), sqrt::cipolla(&v));
+        }
+        // -1 is not a square for p = 3 mod 4
+        assert!(!(-F256::one()).is_square());
+        assert!((-F25519::one()).is_square());
+    }
+}
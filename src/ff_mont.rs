@@ -0,0 +1,313 @@
+//! Prime finite field 𝔽p using the Montgomery representation
+//!
+//! From: https://en.wikipedia.org/wiki/Montgomery_modular_multiplication
+//!
+//! A value a is stored as a * R mod p (with R = 2^(64 * N)) so that a multiplication only
+//! requires a Montgomery reduction (REDC): REDC(aR * bR) = abR (mod p)
+//! and no (slow) division by p
+
+use crate::bigint::{adc, mac, Uint};
+use crate::ff::{forward_ref_binop, forward_ref_unop, FiniteField};
+use crate::ff_big::PrimeModulus;
+use std::fmt;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Prime finite field 𝔽p (p odd) for a (large) prime p made of N u64 limbs,
+/// values are stored in Montgomery form
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct MontFp<M: PrimeModulus<N>, const N: usize> {
+    /// a * R mod p
+    mont_value: Uint<N>,
+    modulus: PhantomData<M>,
+}
+
+impl<M: PrimeModulus<N>, const N: usize> MontFp<M, N> {
+    /// R mod p (aka 1 in Montgomery form)
+    pub const R: Uint<N> = {
+        let mut r = Uint::ONE;
+        let mut i = 0;
+        while i < Uint::<N>::BITS {
+            r = r.double_mod(&M::P);
+            i += 1;
+        }
+        r
+    };
+
+    /// R^2 mod p (used to convert to the Montgomery form)
+    pub const R2: Uint<N> = {
+        let mut r2 = Self::R;
+        let mut i = 0;
+        while i < Uint::<N>::BITS {
+            r2 = r2.double_mod(&M::P);
+            i += 1;
+        }
+        r2
+    };
+
+    /// -p^-1 mod 2^64
+    pub const INV: u64 = {
+        // p^-1 = p^(2^63 - 1) mod 2^64 (as p is odd)
+        let p0 = M::P.limbs()[0];
+        assert!(p0 & 1 == 1, "Montgomery form requires an odd modulus");
+
+        let mut inv = 1u64;
+        let mut i = 0;
+        while i < 63 {
+            inv = inv.wrapping_mul(inv);
+            inv = inv.wrapping_mul(p0);
+            i += 1;
+        }
+        inv.wrapping_neg()
+    };
+
+    pub fn try_new(value: Uint<N>) -> Result<Self, &'static str> {
+        if value < M::P {
+            Ok(Self::from_mont_unchecked(Self::to_montgomery(&value)))
+        } else {
+            Err("Value must be lower than P")
+        }
+    }
+
+    const fn from_mont_unchecked(mont_value: Uint<N>) -> Self {
+        Self {
+            mont_value,
+            modulus: PhantomData,
+        }
+    }
+
+    /// a -> aR mod p (computed as REDC(a * R^2))
+    fn to_montgomery(value: &Uint<N>) -> Uint<N> {
+        let (low, high) = value.mul_schoolbook(&Self::R2);
+        Self::montgomery_reduce(&low, &high)
+    }
+
+    /// aR -> a (computed as REDC(aR))
+    fn from_montgomery(mont_value: &Uint<N>) -> Uint<N> {
+        Self::montgomery_reduce(mont_value, &Uint::ZERO)
+    }
+
+    /// Montgomery reduction (REDC): compute T * R^-1 mod p for T < p * R
+    /// (T = high * 2^(64 * N) + low)
+    fn montgomery_reduce(low: &Uint<N>, high: &Uint<N>) -> Uint<N> {
+        let p = M::P.limbs();
+        let mut t = [*low.limbs(), *high.limbs()];
+        // Carry out of the 2N limbs
+        let mut carry2 = 0;
+
+        for i in 0..N {
+            // Choose k such that T + k * p * 2^(64 * i) is divisible by 2^(64 * (i + 1))
+            let k = t[0][i].wrapping_mul(Self::INV);
+
+            let mut carry = 0;
+            for (j, pj) in p.iter().enumerate() {
+                let idx = i + j;
+                (t[idx / N][idx % N], carry) = mac(t[idx / N][idx % N], k, *pj, carry);
+            }
+            // limb i + N is always in the high part
+            (t[1][i], carry2) = adc(t[1][i], carry, carry2);
+        }
+
+        // The low part is now 0: T / R is in the high part and is < 2p
+        let res = Uint::from_limbs(t[1]);
+        let (reduced, borrow) = res.overflowing_sub(&M::P);
+        if carry2 == 1 || !borrow {
+            reduced
+        } else {
+            res
+        }
+    }
+}
+
+impl<M: PrimeModulus<N>, const N: usize> FiniteField for MontFp<M, N> {
+    const MODULUS: &'static [u64] = M::P.limbs();
+
+    type ValueType = Uint<N>;
+
+    fn new(value: Self::ValueType) -> Self {
+        Self::try_new(value)
+            .unwrap_or_else(|_| panic!("Cannot init a MontFp point with value higher than P - 1"))
+    }
+
+    fn from_u64(value: u64) -> Self {
+        Self::from_mont_unchecked(Self::to_montgomery(&Uint::from_u64(value).rem(&M::P)))
+    }
+
+    fn min() -> Self {
+        Self::from_mont_unchecked(Uint::ZERO)
+    }
+
+    fn max() -> Self {
+        -Self::one()
+    }
+
+    fn one() -> Self {
+        Self::from_mont_unchecked(Self::R)
+    }
+
+    fn value(&self) -> Self::ValueType {
+        Self::from_montgomery(&self.mont_value)
+    }
+}
+
+impl<M: PrimeModulus<N>, const N: usize> Debug for MontFp<M, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MontFp({:?})", self.value())
+    }
+}
+
+impl<M: PrimeModulus<N>, const N: usize> Add for MontFp<M, N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        // aR + bR = (a + b)R so the addition is the same as for BigFp
+        let (sum, carry) = self.mont_value.overflowing_add(&other.mont_value);
+        let (reduced, borrow) = sum.overflowing_sub(&M::P);
+        let value = if carry || !borrow { reduced } else { sum };
+
+        Self::from_mont_unchecked(value)
+    }
+}
+
+impl<M: PrimeModulus<N>, const N: usize> Sub for MontFp<M, N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        let (diff, borrow) = self.mont_value.overflowing_sub(&other.mont_value);
+        let value = if borrow {
+            diff.overflowing_add(&M::P).0
+        } else {
+            diff
+        };
+
+        Self::from_mont_unchecked(value)
+    }
+}
+
+impl<M: PrimeModulus<N>, const N: usize> Mul for MontFp<M, N> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        // REDC(aR * bR) = abR
+        let (low, high) = self.mont_value.mul_schoolbook(&other.mont_value);
+        Self::from_mont_unchecked(Self::montgomery_reduce(&low, &high))
+    }
+}
+
+impl<M: PrimeModulus<N>, const N: usize> Neg for MontFp<M, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::min() - self
+    }
+}
+
+forward_ref_binop!([M: PrimeModulus<N>, const N: usize] Add, add for MontFp<M, N>);
+forward_ref_binop!([M: PrimeModulus<N>, const N: usize] Sub, sub for MontFp<M, N>);
+forward_ref_binop!([M: PrimeModulus<N>, const N: usize] Mul, mul for MontFp<M, N>);
+forward_ref_unop!([M: PrimeModulus<N>, const N: usize] Neg, neg for MontFp<M, N>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ff_big::BigFp;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct P61;
+    impl PrimeModulus<1> for P61 {
+        const P: Uint<1> = Uint::from_u64(61);
+    }
+
+    /// NIST P-256 prime
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct P256;
+    impl PrimeModulus<4> for P256 {
+        const P: Uint<4> =
+            Uint::from_be_hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+    }
+
+    /// BLS12-381 base field prime
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct P381;
+    impl PrimeModulus<6> for P381 {
+        const P: Uint<6> = Uint::from_be_hex(
+            "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab",
+        );
+    }
+
+    /// 2^127 - 1 (highest bit unset)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct M127;
+    impl PrimeModulus<2> for M127 {
+        const P: Uint<2> = Uint::from_be_hex("7fffffffffffffffffffffffffffffff");
+    }
+    /// 2^128 - 159 (highest bit set)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct P128;
+    impl PrimeModulus<2> for P128 {
+        const P: Uint<2> = Uint::from_be_hex("ffffffffffffffffffffffffffffff61");
+    }
+
+    fn check_against_big<M: PrimeModulus<N>, const N: usize>() {
+        let mut a = BigFp::<M, N>::from_u64(0x1234_5678_9abc_def0);
+        let mut b = BigFp::<M, N>::max();
+        for _ in 0..50 {
+            let ma = MontFp::<M, N>::new(a.value());
+            let mb = MontFp::<M, N>::new(b.value());
+            assert_eq!(ma.value(), a.value());
+            assert_eq!((ma + mb).value(), (a + b).value());
+            assert_eq!((ma - mb).value(), (a - b).value());
+            assert_eq!((ma * mb).value(), (a * b).value());
+            assert_eq!((-ma).value(), (-a).value());
+
+            a = a * b + BigFp::one();
+            b = b * b - a;
+        }
+
+        let ma = MontFp::<M, N>::new(a.value());
+        assert_eq!(
+            ma.mul_inverse().unwrap().value(),
+            a.mul_inverse().unwrap().value()
+        );
+        assert_eq!(
+            ma.square_root().map(|r| (r.0.value(), r.1.value())),
+            a.square_root().map(|r| (r.0.value(), r.1.value()))
+        );
+    }
+
+    #[test]
+    fn test_constants() {
+        type F = MontFp<P61, 1>;
+        // 2^64 mod 61 = 2^(64 mod 60) = 16
+        assert_eq!(F::R, Uint::from_u64(16));
+        assert_eq!(F::R2, Uint::from_u64(16 * 16 % 61));
+        assert_eq!(F::INV.wrapping_mul(61), u64::MAX);
+
+        assert_eq!(F::one().value(), Uint::ONE);
+        assert_eq!(F::max().value(), Uint::from_u64(60));
+        assert_eq!(F::from_u64(63).value(), Uint::from_u64(2));
+    }
+
+    #[test]
+    fn test_against_big() {
+        check_against_big::<P61, 1>();
+        check_against_big::<M127, 2>();
+        check_against_big::<P128, 2>();
+        check_against_big::<P256, 4>();
+        check_against_big::<P381, 6>();
+    }
+
+    #[test]
+    fn test_exhaustive_small() {
+        type F = MontFp<P61, 1>;
+        for a in 0..61 {
+            for b in 0..61 {
+                assert_eq!(
+                    (F::from_u64(a) * F::from_u64(b)).value(),
+                    Uint::from_u64(a * b % 61)
+                );
+            }
+        }
+    }
+}
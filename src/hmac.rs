@@ -0,0 +1,77 @@
+//! HMAC keyed hash (needed by the RFC 6979 deterministic nonces of ECDSA)
+//!
+//! From: https://www.rfc-editor.org/rfc/rfc2104
+
+/// A hash function usable by HMAC (see `sha256.rs` & `sha512.rs`)
+pub trait HashFunction {
+    /// Size in bytes of the blocks processed by the compression function
+    const BLOCK_LEN: usize;
+    /// Size in bytes of the digest
+    const OUTPUT_LEN: usize;
+
+    /// Hash of the concatenation of all the parts
+    fn digest(parts: &[&[u8]]) -> Vec<u8>;
+}
+
+/// HMAC(K, m) = H((K' ^ opad) || H((K' ^ ipad) || m)) with m the concatenation of all the parts
+/// (K' is the key hashed if longer than a block, padded with zeros to the block size)
+pub fn hmac<H: HashFunction>(key: &[u8], parts: &[&[u8]]) -> Vec<u8> {
+    let mut key = match key.len() > H::BLOCK_LEN {
+        true => H::digest(&[key]),
+        false => key.to_vec(),
+    };
+    key.resize(H::BLOCK_LEN, 0);
+
+    let ipad: Vec<u8> = key.iter().map(|b| b ^ 0x36).collect();
+    let opad: Vec<u8> = key.iter().map(|b| b ^ 0x5c).collect();
+
+    let mut inner_parts = vec![ipad.as_slice()];
+    inner_parts.extend_from_slice(parts);
+    let inner = H::digest(&inner_parts);
+
+    H::digest(&[&opad, &inner])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sha256::Sha256;
+    use crate::sha512::Sha512;
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_hmac() {
+        // From: RFC 4231 test cases 1, 2 & 6 (checked with python hmac)
+        let vectors: [(&[u8], &[u8], &str, &str); 3] = [
+            (
+                &[0x0b; 20],
+                b"Hi There",
+                "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+                "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854",
+            ),
+            (
+                b"Jefe",
+                b"what do ya want for nothing?",
+                "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+                "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+            ),
+            (
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First",
+                "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+                "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+            ),
+        ];
+
+        for (key, message, expected256, expected512) in vectors {
+            assert_eq!(to_hex(&hmac::<Sha256>(key, &[message])), expected256);
+            assert_eq!(to_hex(&hmac::<Sha512>(key, &[message])), expected512);
+            // The message can be split in several parts
+            let (m1, m2) = message.split_at(3);
+            assert_eq!(to_hex(&hmac::<Sha256>(key, &[m1, m2])), expected256);
+        }
+    }
+}
@@ -0,0 +1,28 @@
+pub mod bigint;
+pub mod bip340;
+pub mod bsgs;
+pub mod curve;
+pub mod curve23;
+pub mod curve61;
+pub mod ecdsa;
+pub mod ed25519;
+pub mod edwards;
+pub mod edwards25519;
+pub mod ff;
+pub mod ff23;
+pub mod ff25519;
+pub mod ff448;
+pub mod ff61;
+pub mod ff_big;
+pub mod ff_mont;
+pub mod hmac;
+pub mod montgomery;
+pub mod nist;
+pub mod schnorr;
+pub mod secp256k1;
+pub mod sha256;
+pub mod sha512;
+pub mod sqrt;
+pub mod weierstrass;
+pub mod x25519;
+pub mod x448;
@@ -1,8 +1,7 @@
-mod curve61;
-mod ff23;
-mod ff61;
-
-use crate::ff23::FiniteField23Point;
+use rust_ecc_curves::curve::EllipticCurve;
+use rust_ecc_curves::curve23::curve23;
+use rust_ecc_curves::ff::FiniteField;
+use rust_ecc_curves::ff23::FiniteField23Point;
 
 fn main() {
     println!("== Finite Field 23 ==");
@@ -12,4 +11,19 @@ fn main() {
     let pr3 = pa3 + pb3;
 
     println!("pr3: {:?}", pr3);
+
+    println!("== Curve23 points ==");
+
+    let c23 = curve23();
+    for p in c23.points() {
+        let order = c23.point_order(&p);
+        match p.coordinates() {
+            None => println!("O        order: {order}"),
+            Some((x, y)) => println!(
+                "({x:2}, {y:2}) order: {order}",
+                x = x.value(),
+                y = y.value()
+            ),
+        }
+    }
 }
@@ -0,0 +1,150 @@
+//! Elliptic curves in Montgomery form: y^2 = x^3 + A*x^2 + x
+//!
+//! From: https://en.wikipedia.org/wiki/Montgomery_curve
+//!
+//! Only the x-coordinate (aka u-coordinate) is used: the Montgomery ladder can compute
+//! u(k * P) from u(P) without ever computing a y-coordinate (RFC 7748 section 5)
+
+use crate::ff::FiniteField;
+
+use bitvec::prelude::*;
+
+/// A Montgomery curve y^2 = x^3 + A*x^2 + x over the finite field F
+#[derive(Debug, Clone, PartialEq)]
+pub struct MontgomeryCurve<F: FiniteField> {
+    a: F,
+    /// (A - 2) / 4 (used by the ladder doubling)
+    a24: F,
+}
+
+impl<F: FiniteField> MontgomeryCurve<F> {
+    pub fn try_new(a: F) -> Result<Self, &'static str> {
+        // The curve must be non singular: A^2 != 4
+        if a * a == F::from_u64(4) {
+            return Err("Curve is singular: A^2 = 4");
+        }
+
+        let four_inv = F::from_u64(4)
+            .mul_inverse()
+            .ok_or("Field characteristic must not be 2")?;
+
+        Ok(Self {
+            a,
+            a24: (a - F::from_u64(2)) * four_inv,
+        })
+    }
+
+    pub fn new(a: F) -> Self {
+        Self::try_new(a).unwrap_or_else(|e| panic!("Cannot init a MontgomeryCurve: {}", e))
+    }
+
+    pub fn a(&self) -> F {
+        self.a
+    }
+
+    /// Compute u(k * P) from u = u(P) using the x-only Montgomery ladder
+    /// (k: little endian bytes, only the lowest `bits` bits are used)
+    ///
+    /// From: https://www.rfc-editor.org/rfc/rfc7748#section-5
+    ///
+    /// Note: the point at infinity is returned as 0 (as in RFC 7748)
+    /// Note: the conditional swaps are branches, this is not a constant time implementation
+    pub fn ladder(&self, k: &[u8], bits: usize, u: F) -> F {
+        let x1 = u;
+        let (mut x2, mut z2) = (F::one(), F::min());
+        let (mut x3, mut z3) = (u, F::one());
+        let mut swap = false;
+
+        for k_t in k.view_bits::<Lsb0>()[..bits].iter().rev() {
+            swap ^= *k_t;
+            if swap {
+                std::mem::swap(&mut x2, &mut x3);
+                std::mem::swap(&mut z2, &mut z3);
+            }
+            swap = *k_t;
+
+            let a = x2 + z2;
+            let aa = a.square();
+            let b = x2 - z2;
+            let bb = b.square();
+            let e = aa - bb;
+            let c = x3 + z3;
+            let d = x3 - z3;
+            let da = d * a;
+            let cb = c * b;
+            x3 = (da + cb).square();
+            z3 = x1 * (da - cb).square();
+            x2 = aa * bb;
+            z2 = e * (aa + self.a24 * e);
+        }
+
+        if swap {
+            std::mem::swap(&mut x2, &mut x3);
+            std::mem::swap(&mut z2, &mut z3);
+        }
+
+        // x2 / z2 (with 1 / 0 = 0)
+        x2 * z2.mul_inverse().unwrap_or(F::min())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ff::Fp;
+
+    type F61 = Fp<61>;
+
+    #[test]
+    fn test_try_new() {
+        assert!(MontgomeryCurve::try_new(F61::from_u64(2)).is_err());
+        assert!(MontgomeryCurve::try_new(-F61::from_u64(2)).is_err());
+        assert_eq!(MontgomeryCurve::new(F61::from_u64(6)).a24, F61::from_u64(1));
+    }
+
+    #[test]
+    fn test_ladder() {
+        // y^2 = x^3 + 6x^2 + x over 𝔽61: compare the ladder against repeated additions
+        // using the affine addition law (x-coordinate only)
+        let c = MontgomeryCurve::new(F61::from_u64(6));
+        let a = c.a();
+
+        // Find a point P = (x, y) with y != 0
+        let (x, y) = (1..61)
+            .map(F61::from_u64)
+            .find_map(|x| {
+                let rhs = x * x * x + a * x * x + x;
+                rhs.square_root()
+                    .filter(|(y, _)| *y != F61::min())
+                    .map(|(y, _)| (x, y))
+            })
+            .unwrap();
+
+        let add = |p: Option<(F61, F61)>, q: (F61, F61)| -> Option<(F61, F61)> {
+            let (px, py) = match p {
+                None => return Some(q),
+                Some(p) => p,
+            };
+            let (qx, qy) = q;
+            if px == qx && py == -qy {
+                return None;
+            }
+            let lambda = if px == qx {
+                (F61::from_u64(3) * px * px + F61::from_u64(2) * a * px + F61::one())
+                    * (F61::from_u64(2) * py).mul_inverse().unwrap()
+            } else {
+                (qy - py) * (qx - px).mul_inverse().unwrap()
+            };
+            let x3 = lambda * lambda - a - px - qx;
+            let y3 = lambda * (px - x3) - py;
+            Some((x3, y3))
+        };
+
+        let mut kp = None;
+        for k in 1u8..100 {
+            kp = add(kp, (x, y));
+            let expected = kp.map_or(F61::min(), |p| p.0);
+            assert_eq!(c.ladder(&[k], 8, x), expected);
+        }
+    }
+}
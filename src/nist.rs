@@ -0,0 +1,325 @@
+//! NIST P-256 and P-384 curves: y^2 = x^3 - 3x + b
+//!
+//! From: https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-186.pdf (section 3.2.1)
+//!
+//! Both primes are generalized Mersenne primes (aka Solinas primes): a product can be
+//! reduced with a few additions & subtractions of 32 bits words instead of a long division
+//!
+//! From: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.186-4.pdf (appendix D.2)
+
+use crate::bigint::Uint;
+use crate::curve::Point;
+use crate::ff::FiniteField;
+use crate::ff_big::{BigFp, PrimeModulus};
+use crate::ff_mont::MontFp;
+use crate::weierstrass::WeierstrassCurve;
+
+/// A zero word in the reduction tables below
+const Z: usize = usize::MAX;
+
+/// Fast reduction mod a Solinas prime p (of 32 * 2N bits)
+///
+/// The product (high * 2^(64 * N) + low) is split in 32 bits words c_i and the result is
+/// the sum of the given terms: coefficient * (c_j, ..., c_k) with the words listed from the
+/// most significant one (as in FIPS 186-4 appendix D.2)
+fn solinas_reduce<const N: usize>(
+    low: &Uint<N>,
+    high: &Uint<N>,
+    p: &Uint<N>,
+    terms: &[(i64, &[usize])],
+) -> Uint<N> {
+    let words = 2 * N;
+    let c = |i: usize| -> i64 {
+        if i == Z {
+            return 0;
+        }
+        let limb = match i / 2 < N {
+            true => low.limbs()[i / 2],
+            false => high.limbs()[i / 2 - N],
+        };
+        ((limb >> (32 * (i % 2))) & 0xffff_ffff) as i64
+    };
+
+    // Signed sum of the terms, word by word (each word stays far below 2^63)
+    let mut acc = vec![0i64; words];
+    for (coefficient, term) in terms {
+        for (k, i) in term.iter().enumerate() {
+            acc[words - 1 - k] += coefficient * c(*i);
+        }
+    }
+
+    // Carry propagation: result = carry * 2^(64 * N) + limbs
+    let mut limbs = [0u64; N];
+    let mut carry = 0i64;
+    for (i, word) in acc.iter().enumerate() {
+        let v = word + carry;
+        limbs[i / 2] |= ((v & 0xffff_ffff) as u64) << (32 * (i % 2));
+        carry = v >> 32;
+    }
+    let mut res = Uint::from_limbs(limbs);
+
+    // The carry is small: a few additions or subtractions of p bring the result in [0; p - 1]
+    while carry < 0 {
+        let (sum, overflow) = res.overflowing_add(p);
+        res = sum;
+        carry += overflow as i64;
+    }
+    while carry > 0 || res >= *p {
+        let (diff, borrow) = res.overflowing_sub(p);
+        res = diff;
+        carry -= borrow as i64;
+    }
+
+    res
+}
+
+/// p = 2^256 - 2^224 + 2^192 + 2^96 - 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct P256P;
+impl PrimeModulus<4> for P256P {
+    const P: Uint<4> =
+        Uint::from_be_hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+
+    /// From: FIPS 186-4 appendix D.2.3 (T + 2S1 + 2S2 + S3 + S4 - D1 - D2 - D3 - D4)
+    fn reduce_wide(low: &Uint<4>, high: &Uint<4>) -> Uint<4> {
+        solinas_reduce(
+            low,
+            high,
+            &Self::P,
+            &[
+                (1, &[7, 6, 5, 4, 3, 2, 1, 0]),
+                (2, &[15, 14, 13, 12, 11, Z, Z, Z]),
+                (2, &[Z, 15, 14, 13, 12, Z, Z, Z]),
+                (1, &[15, 14, Z, Z, Z, 10, 9, 8]),
+                (1, &[8, 13, 15, 14, 13, 11, 10, 9]),
+                (-1, &[10, 8, Z, Z, Z, 13, 12, 11]),
+                (-1, &[11, 9, Z, Z, 15, 14, 13, 12]),
+                (-1, &[12, Z, 10, 9, 8, 15, 14, 13]),
+                (-1, &[13, Z, 11, 10, 9, Z, 15, 14]),
+            ],
+        )
+    }
+}
+
+/// n: the order of the P-256 base point (the group order as the cofactor is 1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct P256N;
+impl PrimeModulus<4> for P256N {
+    const P: Uint<4> =
+        Uint::from_be_hex("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551");
+}
+
+/// p = 2^384 - 2^128 - 2^96 + 2^32 - 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct P384P;
+impl PrimeModulus<6> for P384P {
+    const P: Uint<6> = Uint::from_be_hex(
+        "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffff0000000000000000ffffffff",
+    );
+
+    /// From: FIPS 186-4 appendix D.2.4 (T + 2S1 + S2 + S3 + S4 + S5 + S6 - D1 - D2 - D3)
+    fn reduce_wide(low: &Uint<6>, high: &Uint<6>) -> Uint<6> {
+        solinas_reduce(
+            low,
+            high,
+            &Self::P,
+            &[
+                (1, &[11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0]),
+                (2, &[Z, Z, Z, Z, Z, 23, 22, 21, Z, Z, Z, Z]),
+                (1, &[23, 22, 21, 20, 19, 18, 17, 16, 15, 14, 13, 12]),
+                (1, &[20, 19, 18, 17, 16, 15, 14, 13, 12, 23, 22, 21]),
+                (1, &[19, 18, 17, 16, 15, 14, 13, 12, 20, Z, 23, Z]),
+                (1, &[Z, Z, Z, Z, 23, 22, 21, 20, Z, Z, Z, Z]),
+                (1, &[Z, Z, Z, Z, Z, Z, 23, 22, 21, Z, Z, 20]),
+                (-1, &[22, 21, 20, 19, 18, 17, 16, 15, 14, 13, 12, 23]),
+                (-1, &[Z, Z, Z, Z, Z, Z, Z, 23, 22, 21, 20, Z]),
+                (-1, &[Z, Z, Z, Z, Z, Z, Z, 23, 23, Z, Z, Z]),
+            ],
+        )
+    }
+}
+
+/// n: the order of the P-384 base point (the group order as the cofactor is 1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct P384N;
+impl PrimeModulus<6> for P384N {
+    const P: Uint<6> = Uint::from_be_hex(
+        "ffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf581a0db248b0a77aecec196accc52973",
+    );
+}
+
+/// P-256 base field 𝔽p (with the Solinas reduction)
+pub type P256Field = BigFp<P256P, 4>;
+/// P-256 scalar field 𝔽n
+pub type P256Scalar = MontFp<P256N, 4>;
+
+pub type P256 = WeierstrassCurve<P256Field, P256Scalar>;
+
+/// P-384 base field 𝔽p (with the Solinas reduction)
+pub type P384Field = BigFp<P384P, 6>;
+/// P-384 scalar field 𝔽n
+pub type P384Scalar = MontFp<P384N, 6>;
+
+pub type P384 = WeierstrassCurve<P384Field, P384Scalar>;
+
+/// P-256 (aka secp256r1) with its standard base point G
+pub fn p256() -> P256 {
+    // Checked by test_order: the base point is on the curve with the order n
+    WeierstrassCurve::new_unchecked(
+        -P256Field::from_u64(3),
+        P256Field::new(Uint::from_be_hex(
+            "5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+        )),
+        Point::new(
+            P256Field::new(Uint::from_be_hex(
+                "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+            )),
+            P256Field::new(Uint::from_be_hex(
+                "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+            )),
+        ),
+        1,
+    )
+}
+
+/// P-384 (aka secp384r1) with its standard base point G
+pub fn p384() -> P384 {
+    // Checked by test_order: the base point is on the curve with the order n
+    WeierstrassCurve::new_unchecked(
+        -P384Field::from_u64(3),
+        P384Field::new(Uint::from_be_hex(
+            "b3312fa7e23ee7e4988e056be3f82d19181d9c6efe8141120314088f5013875ac656398d8a2ed19d2a85c8edd3ec2aef",
+        )),
+        Point::new(
+            P384Field::new(Uint::from_be_hex(
+                "aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a385502f25dbf55296c3a545e3872760ab7",
+            )),
+            P384Field::new(Uint::from_be_hex(
+                "3617de4a96262c6f5d9e98bf9292dc29f8f41dbd289a147ce9da3113b5f0b8c00a60b1ce1d7e819d7a431d7c90ea0e5f",
+            )),
+        ),
+        1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::EllipticCurve;
+    use std::collections::HashMap;
+
+    fn check_reduction<M: PrimeModulus<N>, const N: usize>() {
+        // Compare with the plain long division (including the largest product (p - 1)^2)
+        let max = M::P.overflowing_sub(&Uint::ONE).0;
+        let mut a = max;
+        let mut b = max;
+        for _ in 0..1000 {
+            let (low, high) = a.mul_schoolbook(&b);
+            let expected = Uint::rem_wide(&low, &high, &M::P);
+            assert_eq!(M::reduce_wide(&low, &high), expected);
+
+            a = expected;
+            b = Uint::rem_wide(&high, &low, &M::P)
+                .overflowing_add(&Uint::ONE)
+                .0;
+        }
+
+        for (a, b) in [(Uint::ZERO, max), (Uint::ONE, max), (max, Uint::ONE)] {
+            let (low, high) = a.mul_schoolbook(&b);
+            assert_eq!(
+                M::reduce_wide(&low, &high),
+                Uint::rem_wide(&low, &high, &M::P)
+            );
+        }
+    }
+
+    #[test]
+    fn test_solinas_reduction() {
+        check_reduction::<P256P, 4>();
+        check_reduction::<P384P, 6>();
+    }
+
+    fn check_order<C: EllipticCurve>(c: &C) {
+        // (n - 1) * G = -G so n * G = O
+        let g = c.base_point();
+        assert_eq!(c.point_mul(g, C::ScalarType::max()), c.point_neg(&g));
+        assert_eq!(c.point_mul_ladder(g, C::ScalarType::max()), c.point_neg(&g));
+        assert_eq!(c.point_add(&c.point_neg(&g), &g), Point::Identity);
+        assert_eq!(c.point_double(&g), c.point_add(&g, &g));
+    }
+
+    #[test]
+    fn test_order() {
+        check_order(&p256());
+        check_order(&p384());
+
+        // p256() & p384() skip the checks of try_new
+        let (c256, c384) = (p256(), p384());
+        assert_eq!(
+            P256::try_new(c256.a(), c256.b(), c256.base_point(), 1),
+            Ok(c256)
+        );
+        assert_eq!(
+            P384::try_new(c384.a(), c384.b(), c384.base_point(), 1),
+            Ok(c384)
+        );
+    }
+
+    /// Parse the [P-xxx] sections of a CAVP file: (curve name, key = value entries) per COUNT
+    fn parse_cavp(text: &str) -> Vec<(String, HashMap<String, String>)> {
+        let mut vectors = Vec::new();
+        let mut curve = String::new();
+        for line in text.lines().map(str::trim) {
+            if line.starts_with('#') || line.is_empty() {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                curve = name.to_string();
+            } else if let Some((key, value)) = line.split_once('=') {
+                let (key, value) = (key.trim().to_string(), value.trim().to_string());
+                if key == "COUNT" {
+                    vectors.push((curve.clone(), HashMap::new()));
+                }
+                vectors.last_mut().unwrap().1.insert(key, value);
+            }
+        }
+        vectors
+    }
+
+    /// dIUT * G = QIUT and the shared secret x(dIUT * QCAVS) = ZIUT
+    fn check_cdh<F: FiniteField<ValueType = Uint<N>>, S, const N: usize>(
+        c: &WeierstrassCurve<F, S>,
+        v: &HashMap<String, String>,
+    ) where
+        S: FiniteField<ValueType = Uint<N>>,
+        WeierstrassCurve<F, S>: EllipticCurve<ValueType = F, ScalarType = S>,
+    {
+        let coordinate = |key: &str| F::new(Uint::from_be_hex(&v[key]));
+        let d = S::new(Uint::from_be_hex(&v["dIUT"]));
+        let q_cavs = Point::new(coordinate("QCAVSx"), coordinate("QCAVSy"));
+        let q_iut = Point::new(coordinate("QIUTx"), coordinate("QIUTy"));
+
+        assert!(c.is_on_curve(&q_cavs));
+        assert_eq!(c.point_mul(c.base_point(), d), q_iut);
+        let (z, _) = c.point_mul(q_cavs, d).coordinates().unwrap();
+        assert_eq!(z, coordinate("ZIUT"));
+    }
+
+    #[test]
+    fn test_cavp_ecc_cdh() {
+        // From: NIST CAVP KAS ECC CDH primitive test vectors (a subset, see the file header)
+        let vectors = parse_cavp(include_str!(
+            "../test_vectors/KAS_ECC_CDH_PrimitiveTest.txt"
+        ));
+        assert_eq!(vectors.len(), 6);
+
+        let (c256, c384) = (p256(), p384());
+        for (curve, v) in &vectors {
+            match curve.as_str() {
+                "P-256" => check_cdh(&c256, v),
+                "P-384" => check_cdh(&c384, v),
+                _ => panic!("Unexpected curve {}", curve),
+            }
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! Schnorr signatures over any `EllipticCurve` (small enough to be checked by hand on Curve61)
+//!
+//! From: https://en.wikipedia.org/wiki/Schnorr_signature
+//!
+//! With the private key x & the public key P = x * G:
+//! * sign: R = k * G (k: a secret nonce), e = H(R || P || m) & s = k + e * x
+//! * verify: s * G = R + e * P (as s * G = k * G + e * x * G)
+//!
+//! The nonce must never be reused: two signatures (R, s1) & (R, s2) with the same k reveal
+//! x = (s1 - s2) / (e1 - e2)
+
+use crate::curve::{EllipticCurve, Point};
+use crate::ecdsa::be_bytes_to_limbs;
+use crate::ff::FiniteField;
+use crate::hmac::HashFunction;
+use std::marker::PhantomData;
+
+/// Hash to a scalar: how the challenge e is computed from the bytes of R, P & m
+pub trait HashToScalar {
+    fn hash_to_scalar<S: FiniteField>(parts: &[&[u8]]) -> S;
+}
+
+/// The sum of all the bytes mod n: easy to compute by hand but not a real hash function
+/// (e.g. "ab" & "ba" have the same sum: a signature of one is a signature of the other)
+pub struct ByteSum;
+
+impl HashToScalar for ByteSum {
+    fn hash_to_scalar<S: FiniteField>(parts: &[&[u8]]) -> S {
+        parts
+            .iter()
+            .flat_map(|part| part.iter())
+            .fold(S::min(), |e, b| e + S::from_u64(*b as u64))
+    }
+}
+
+/// The digest of the hash function H as a big endian integer reduced mod n
+pub struct HashReduce<H: HashFunction>(PhantomData<H>);
+
+impl<H: HashFunction> HashToScalar for HashReduce<H> {
+    fn hash_to_scalar<S: FiniteField>(parts: &[&[u8]]) -> S {
+        S::from_le_limbs(&be_bytes_to_limbs(&H::digest(parts)))
+    }
+}
+
+/// A Schnorr signature (R, s)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Signature<F: FiniteField, S: FiniteField> {
+    pub r: Point<F>,
+    pub s: S,
+}
+
+/// Point bytes: x || y (big endian, 1 byte each on Curve61), nothing for the point at infinity
+fn point_bytes<F: FiniteField>(p: &Point<F>) -> Vec<u8> {
+    match p.coordinates() {
+        None => vec![],
+        Some((x, y)) => [x.to_be_bytes(), y.to_be_bytes()].concat(),
+    }
+}
+
+/// e = H(R || P || m)
+pub fn challenge<C: EllipticCurve, H: HashToScalar>(
+    r: &Point<C::ValueType>,
+    public: &Point<C::ValueType>,
+    message: &[u8],
+) -> C::ScalarType {
+    H::hash_to_scalar(&[&point_bytes(r), &point_bytes(public), message])
+}
+
+/// Public key: P = x * G
+pub fn public_key<C: EllipticCurve>(c: &C, x: C::ScalarType) -> Point<C::ValueType> {
+    c.point_mul(c.base_point(), x)
+}
+
+/// Sign a message with the private key x & the nonce k (both != 0, k must be secret & used once)
+pub fn sign<C: EllipticCurve, H: HashToScalar>(
+    c: &C,
+    x: C::ScalarType,
+    k: C::ScalarType,
+    message: &[u8],
+) -> Result<Signature<C::ValueType, C::ScalarType>, &'static str> {
+    if x == C::ScalarType::min() {
+        return Err("Invalid private key: x = 0");
+    }
+    if k == C::ScalarType::min() {
+        return Err("Invalid nonce: k = 0");
+    }
+
+    let r = c.point_mul(c.base_point(), k);
+    let e = challenge::<C, H>(&r, &public_key(c, x), message);
+    Ok(Signature { r, s: k + e * x })
+}
+
+/// Verify a signature with the public key P: s * G = R + e * P
+pub fn verify<C: EllipticCurve, H: HashToScalar>(
+    c: &C,
+    public: &Point<C::ValueType>,
+    message: &[u8],
+    signature: &Signature<C::ValueType, C::ScalarType>,
+) -> Result<(), &'static str> {
+    if public.is_identity() {
+        return Err("Invalid public key");
+    }
+    if signature.r.is_identity() {
+        return Err("Invalid signature: R is the identity");
+    }
+
+    let e = challenge::<C, H>(&signature.r, public, message);
+    let lhs = c.point_mul(c.base_point(), signature.s);
+    let rhs = c.point_add(&signature.r, &c.point_mul(*public, e));
+
+    match lhs == rhs {
+        true => Ok(()),
+        false => Err("Invalid signature"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve23::{curve23, Curve23Scalar};
+    use crate::curve61::{curve61, Curve61, Curve61Scalar};
+    use crate::ff23::FiniteField23Point;
+    use crate::ff61::FiniteField61Point;
+    use crate::sha256::Sha256;
+
+    #[test]
+    fn test_curve61_by_hand() {
+        // x = 7: P = 7 * (5, 7) = (52, 44), k = 12: R = 12 * (5, 7) = (60, 28)
+        // e = 60 + 28 + 52 + 44 + 'h' + 'i' = 393 = 28 mod 73
+        // s = k + e * x = 12 + 28 * 7 = 208 = 62 mod 73
+        let c = curve61();
+        let f = FiniteField61Point::new;
+        let s = Curve61Scalar::new;
+
+        let public = public_key(&c, s(7));
+        assert_eq!(public, Point::new(f(52), f(44)));
+        let signature = sign::<_, ByteSum>(&c, s(7), s(12), b"hi").unwrap();
+        assert_eq!(
+            challenge::<Curve61, ByteSum>(&signature.r, &public, b"hi"),
+            s(28)
+        );
+        assert_eq!(
+            signature,
+            Signature {
+                r: Point::new(f(60), f(28)),
+                s: s(62)
+            }
+        );
+        assert_eq!(verify::<_, ByteSum>(&c, &public, b"hi", &signature), Ok(()));
+
+        // Not a hash function: same byte sum, same challenge
+        assert_eq!(verify::<_, ByteSum>(&c, &public, b"ih", &signature), Ok(()));
+        assert!(verify::<_, ByteSum>(&c, &public, b"hj", &signature).is_err());
+
+        // With SHA-256 (computed with python): e = 4 & s = 12 + 4 * 7 = 40
+        let signature = sign::<_, HashReduce<Sha256>>(&c, s(7), s(12), b"hi").unwrap();
+        assert_eq!(signature.s, s(40));
+        assert_eq!(
+            verify::<_, HashReduce<Sha256>>(&c, &public, b"hi", &signature),
+            Ok(())
+        );
+        assert!(verify::<_, HashReduce<Sha256>>(&c, &public, b"ih", &signature).is_err());
+    }
+
+    #[test]
+    fn test_curve23_by_hand() {
+        // x = 5: P = (7, 20), k = 9: R = (4, 7), e = 4 + 7 + 7 + 20 + 'h' + 'i' = 15 mod 29
+        // s = 9 + 15 * 5 = 84 = 26 mod 29
+        let c = curve23();
+        let f = FiniteField23Point::new;
+        let s = Curve23Scalar::new;
+
+        let public = public_key(&c, s(5));
+        assert_eq!(public, Point::new(f(7), f(20)));
+        let signature = sign::<_, ByteSum>(&c, s(5), s(9), b"hi").unwrap();
+        assert_eq!(
+            signature,
+            Signature {
+                r: Point::new(f(4), f(7)),
+                s: s(26)
+            }
+        );
+        assert_eq!(verify::<_, ByteSum>(&c, &public, b"hi", &signature), Ok(()));
+
+        // Every private key & nonce
+        for x in 1..29 {
+            let public = public_key(&c, s(x));
+            for k in 1..29 {
+                let signature = sign::<_, ByteSum>(&c, s(x), s(k), b"hi").unwrap();
+                assert_eq!(verify::<_, ByteSum>(&c, &public, b"hi", &signature), Ok(()));
+                let forged = Signature {
+                    s: signature.s + Curve23Scalar::one(),
+                    ..signature
+                };
+                assert!(verify::<_, ByteSum>(&c, &public, b"hi", &forged).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn test_nonce_reuse() {
+        // Same k for two messages: x = (s1 - s2) / (e1 - e2)
+        let c = curve61();
+        let s = Curve61Scalar::new;
+        let (x, k) = (s(42), s(30));
+        let public = public_key(&c, x);
+
+        let sig1 = sign::<_, ByteSum>(&c, x, k, b"hello").unwrap();
+        let sig2 = sign::<_, ByteSum>(&c, x, k, b"world").unwrap();
+        let e1 = challenge::<Curve61, ByteSum>(&sig1.r, &public, b"hello");
+        let e2 = challenge::<Curve61, ByteSum>(&sig2.r, &public, b"world");
+        assert_eq!((e1, sig1.s), (s(24), s(16)));
+        assert_eq!((sig1.s - sig2.s) * (e1 - e2).mul_inverse().unwrap(), x);
+    }
+
+    #[test]
+    fn test_invalid() {
+        let c = curve61();
+        let s = Curve61Scalar::new;
+        assert!(sign::<_, ByteSum>(&c, s(0), s(12), b"hi").is_err());
+        assert!(sign::<_, ByteSum>(&c, s(7), s(0), b"hi").is_err());
+
+        let signature = sign::<_, ByteSum>(&c, s(7), s(12), b"hi").unwrap();
+        assert!(verify::<_, ByteSum>(&c, &Point::Identity, b"hi", &signature).is_err());
+        let identity = Signature {
+            r: Point::Identity,
+            s: s(0),
+        };
+        assert!(verify::<_, ByteSum>(&c, &public_key(&c, s(7)), b"hi", &identity).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+//! secp256k1: the Bitcoin curve y^2 = x^3 + 7
+//!
+//! From: https://www.secg.org/sec2-v2.pdf (section 2.4.1)
+
+use crate::bigint::Uint;
+use crate::curve::Point;
+use crate::ff::FiniteField;
+use crate::ff_big::PrimeModulus;
+use crate::ff_mont::MontFp;
+use crate::weierstrass::WeierstrassCurve;
+
+/// p = 2^256 - 2^32 - 977
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Secp256k1P;
+impl PrimeModulus<4> for Secp256k1P {
+    const P: Uint<4> =
+        Uint::from_be_hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f");
+}
+
+/// n: the order of the base point (the group order as the cofactor is 1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Secp256k1N;
+impl PrimeModulus<4> for Secp256k1N {
+    const P: Uint<4> =
+        Uint::from_be_hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+}
+
+/// Base field 𝔽p
+pub type Secp256k1Field = MontFp<Secp256k1P, 4>;
+/// Scalar field 𝔽n
+pub type Secp256k1Scalar = MontFp<Secp256k1N, 4>;
+
+pub type Secp256k1 = WeierstrassCurve<Secp256k1Field, Secp256k1Scalar>;
+
+/// secp256k1 with its standard base point G
+pub fn secp256k1() -> Secp256k1 {
+    // Checked by test_order: the base point is on the curve with the order n
+    WeierstrassCurve::new_unchecked(
+        Secp256k1Field::min(),
+        Secp256k1Field::from_u64(7),
+        Point::new(
+            Secp256k1Field::new(Uint::from_be_hex(
+                "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            )),
+            Secp256k1Field::new(Uint::from_be_hex(
+                "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+            )),
+        ),
+        1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::EllipticCurve;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn scalar(hex: &str) -> Secp256k1Scalar {
+        Secp256k1Scalar::new(Uint::from_be_hex(hex))
+    }
+
+    #[test]
+    fn test_order() {
+        let c = secp256k1();
+        let g = c.base_point();
+
+        // (n - 1) * G = -G so n * G = O
+        assert_eq!(c.point_mul(g, Secp256k1Scalar::max()), c.point_neg(&g));
+        assert_eq!(c.point_add(&c.point_neg(&g), &g), Point::Identity);
+        assert_eq!(c.point_mul(g, Secp256k1Scalar::min()), Point::Identity);
+
+        // secp256k1() skips the checks of try_new
+        assert_eq!(Secp256k1::try_new(c.a(), c.b(), g, 1), Ok(c));
+    }
+
+    #[test]
+    fn test_public_keys() {
+        // Public keys (SEC1 compressed & uncompressed) computed with python cryptography
+        let c = secp256k1();
+        let g = c.base_point();
+
+        let vectors = [
+            (
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+                "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+            ),
+            (
+                "0000000000000000000000000000000000000000000000000000000000000002",
+                "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+                "04c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee51ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a",
+            ),
+            (
+                "0000000000000000000000000000000000000000000000000000000000000006",
+                "03fff97bd5755eeea420453a14355235d382f6472f8568a18b2f057a1460297556",
+                "04fff97bd5755eeea420453a14355235d382f6472f8568a18b2f057a1460297556ae12777aacfbb620f3be96017f45c560de80f0f6518fe4a03c870c36b075f297",
+            ),
+            (
+                "00000000000000000000000000000000000000000000000000000000deadbeef",
+                "0276d2fdf1302d1fa9556f4df94ec84cefba6d482e54f47c6c2a238c1baa560f0e",
+                "0476d2fdf1302d1fa9556f4df94ec84cefba6d482e54f47c6c2a238c1baa560f0eb754ac7e7a3e09c44184cb451a4f5fb557f32053eb015dffebb655b5cfd54d8a",
+            ),
+            (
+                "c0ffee0123456789abcdef0123456789abcdef0123456789abcdef0123456789",
+                "0286b426457e804e474c4045d62834e2a3f733b2ebf4b11ec532a2c4a9d573da7b",
+                "0486b426457e804e474c4045d62834e2a3f733b2ebf4b11ec532a2c4a9d573da7b6853df18f82615250296f561cf78ad6cab34a4896f078b0b2e3003c50ff734b2",
+            ),
+        ];
+
+        for (d, compressed, uncompressed) in vectors {
+            let q = c.point_mul(g, scalar(d));
+            assert_eq!(c.to_sec1_bytes(&q, true), from_hex(compressed));
+            assert_eq!(c.to_sec1_bytes(&q, false), from_hex(uncompressed));
+            assert_eq!(c.from_sec1_bytes(&from_hex(compressed)), Ok(q));
+            assert_eq!(c.from_sec1_bytes(&from_hex(uncompressed)), Ok(q));
+        }
+    }
+
+    #[test]
+    fn test_sec1_invalid() {
+        let c = secp256k1();
+
+        // G with a modified y is not on the curve
+        let mut bytes = from_hex("0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b9");
+        assert_eq!(c.from_sec1_bytes(&bytes), Err("Point is not on the curve"));
+        bytes.pop();
+        assert_eq!(c.from_sec1_bytes(&bytes), Err("Invalid SEC1 encoding"));
+
+        // x = p is not a valid coordinate
+        let bytes = from_hex("02fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f");
+        assert_eq!(c.from_sec1_bytes(&bytes), Err("Invalid x coordinate"));
+
+        // x = 5: 5^3 + 7 = 132 is not a square mod p (computed with python)
+        let bytes = from_hex("020000000000000000000000000000000000000000000000000000000000000005");
+        assert_eq!(c.from_sec1_bytes(&bytes), Err("Point is not on the curve"));
+    }
+}
@@ -0,0 +1,232 @@
+//! Square roots in a prime finite field 𝔽p
+//!
+//! From: https://en.wikipedia.org/wiki/Tonelli%E2%80%93Shanks_algorithm
+//!  and: https://en.wikipedia.org/wiki/Cipolla%27s_algorithm
+
+use crate::bigint::{add_word, shr, sub_word, trailing_zeros};
+use crate::ff::FiniteField;
+
+/// Legendre symbol of n (computed with Euler's criterion: n^((p - 1) / 2))
+/// Return 0 if n == 0, 1 if n is a (non zero) square, -1 otherwise
+pub fn legendre_symbol<F: FiniteField>(n: &F) -> i8 {
+    if *n == F::min() {
+        return 0;
+    }
+
+    if n.pow(&shr(&sub_word(F::MODULUS, 1), 1)) == F::one() {
+        1
+    } else {
+        -1
+    }
+}
+
+/// Return true if n is a square (aka a quadratic residue) in 𝔽p
+pub fn is_square<F: FiniteField>(n: &F) -> bool {
+    legendre_symbol(n) >= 0
+}
+
+/// Return (r, -r) ordered by value (lowest value first)
+pub fn canonical_roots<F: FiniteField>(r: F) -> (F, F) {
+    let neg_r = -r;
+    if r.value() <= neg_r.value() {
+        (r, neg_r)
+    } else {
+        (neg_r, r)
+    }
+}
+
+/// Find the smallest quadratic non residue of 𝔽p (p > 2)
+fn non_residue<F: FiniteField>() -> F {
+    let mut z = F::one() + F::one();
+    while legendre_symbol(&z) != -1 {
+        z = z + F::one();
+    }
+    z
+}
+
+/// Square root of n using the Tonelli-Shanks algorithm
+/// Use the fast paths for p = 3 mod 4 and p = 5 mod 8 (Atkin) when possible
+pub fn tonelli_shanks<F: FiniteField>(n: &F) -> Option<(F, F)> {
+    if F::MODULUS == [2] || *n == F::min() {
+        return Some((*n, *n));
+    }
+    if !is_square(n) {
+        return None;
+    }
+
+    let r = match F::MODULUS[0] % 8 {
+        3 | 7 => {
+            // p = 3 mod 4: r = n^((p + 1) / 4)
+            n.pow(&shr(&add_word(F::MODULUS, 1), 2))
+        }
+        5 => {
+            // p = 5 mod 8 (Atkin): g = (2n)^((p - 5) / 8), i = 2n * g^2, r = n * g * (i - 1)
+            let n_2 = *n + *n;
+            let g = n_2.pow(&shr(&sub_word(F::MODULUS, 5), 3));
+            let i = n_2 * g * g;
+            *n * g * (i - F::one())
+        }
+        _ => {
+            // Write p - 1 = q * 2^s with q odd
+            let p_minus_1 = sub_word(F::MODULUS, 1);
+            let s = trailing_zeros(&p_minus_1);
+            let q = shr(&p_minus_1, s);
+
+            let mut m = s;
+            let mut c = non_residue::<F>().pow(&q);
+            let mut t = n.pow(&q);
+            // r = n^((q + 1) / 2)
+            let mut r = n.pow(&shr(&add_word(&q, 1), 1));
+
+            while t != F::one() {
+                // Find the least i (0 < i < m) such that t^(2^i) = 1
+                let mut i = 0;
+                let mut t_pow = t;
+                while t_pow != F::one() {
+                    t_pow = t_pow * t_pow;
+                    i += 1;
+                }
+
+                // b = c^(2^(m - i - 1))
+                let mut b = c;
+                for _ in 0..(m - i - 1) {
+                    b = b * b;
+                }
+
+                m = i;
+                c = b * b;
+                t = t * c;
+                r = r * b;
+            }
+
+            r
+        }
+    };
+
+    Some(canonical_roots(r))
+}
+
+/// An element of 𝔽p²: a + b * w where w * w = W (W being a non residue in 𝔽p)
+#[derive(Clone, Copy)]
+struct Fp2Point<F: FiniteField> {
+    a: F,
+    b: F,
+}
+
+impl<F: FiniteField> Fp2Point<F> {
+    fn mul(&self, other: &Self, w_square: F) -> Self {
+        Self {
+            a: self.a * other.a + self.b * other.b * w_square,
+            b: self.a * other.b + self.b * other.a,
+        }
+    }
+}
+
+/// Square root of n using Cipolla's algorithm
+pub fn cipolla<F: FiniteField>(n: &F) -> Option<(F, F)> {
+    if F::MODULUS == [2] || *n == F::min() {
+        return Some((*n, *n));
+    }
+    if !is_square(n) {
+        return None;
+    }
+
+    // Find a such that a^2 - n is not a square
+    let mut a = F::min();
+    while legendre_symbol(&(a * a - *n)) != -1 {
+        a = a + F::one();
+    }
+    let w_square = a * a - *n;
+
+    // r = (a + w)^((p + 1) / 2)
+    let mut res = Fp2Point {
+        a: F::one(),
+        b: F::min(),
+    };
+    let mut base = Fp2Point { a, b: F::one() };
+    for limb in shr(&add_word(F::MODULUS, 1), 1) {
+        let mut limb = limb;
+        for _ in 0..64 {
+            if limb & 1 == 1 {
+                res = res.mul(&base, w_square);
+            }
+            base = base.mul(&base, w_square);
+            limb >>= 1;
+        }
+    }
+
+    // The w part of res is always 0
+    debug_assert_eq!(res.b, F::min());
+    Some(canonical_roots(res.a))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ff::Fp;
+
+    // p = 3 mod 4
+    type F43 = Fp<43>;
+    // p = 5 mod 8
+    type F37 = Fp<37>;
+    // p = 1 mod 8
+    type F41 = Fp<41>;
+    // p = 1 mod 2^16 (s = 16 for Tonelli-Shanks)
+    type F65537 = Fp<65537>;
+    // p = 1 mod 2^32 (Goldilocks prime: 2^64 - 2^32 + 1)
+    type FGold = Fp<18446744069414584321>;
+
+    fn check_sqrt<F: FiniteField>(
+        sqrt_fn: fn(&F) -> Option<(F, F)>,
+        values: impl Iterator<Item = F>,
+    ) {
+        for v in values {
+            let res = sqrt_fn(&v);
+            assert_eq!(res.is_some(), is_square(&v));
+            if let Some((r1, r2)) = res {
+                assert_eq!(r1 * r1, v);
+                assert_eq!(r2 * r2, v);
+                assert_eq!(r1 + r2, F::min());
+                assert!(r1.value() <= r2.value());
+            }
+        }
+    }
+
+    #[test]
+    fn test_legendre_symbol() {
+        assert_eq!(legendre_symbol(&F43::min()), 0);
+        assert_eq!(legendre_symbol(&F43::new(4)), 1);
+        assert_eq!(legendre_symbol(&F43::max()), -1);
+        assert_eq!(legendre_symbol(&F41::max()), 1);
+
+        // exactly (p - 1) / 2 non zero squares
+        let count = (1..F41::P).filter(|v| is_square(&F41::new(*v))).count();
+        assert_eq!(count as u64, (F41::P - 1) / 2);
+    }
+
+    #[test]
+    fn test_tonelli_shanks() {
+        check_sqrt(tonelli_shanks, (0..F43::P).map(F43::new));
+        check_sqrt(tonelli_shanks, (0..F37::P).map(F37::new));
+        check_sqrt(tonelli_shanks, (0..F41::P).map(F41::new));
+        check_sqrt(tonelli_shanks, (0..F65537::P).step_by(97).map(F65537::new));
+        check_sqrt(tonelli_shanks, (0..1000).map(FGold::new));
+        check_sqrt(tonelli_shanks, (0..Fp::<2>::P).map(Fp::<2>::new));
+    }
+
+    #[test]
+    fn test_cipolla() {
+        check_sqrt(cipolla, (0..F43::P).map(F43::new));
+        check_sqrt(cipolla, (0..F37::P).map(F37::new));
+        check_sqrt(cipolla, (0..F41::P).map(F41::new));
+        check_sqrt(cipolla, (0..F65537::P).step_by(97).map(F65537::new));
+        check_sqrt(cipolla, (0..1000).map(FGold::new));
+    }
+
+    #[test]
+    fn test_same_roots() {
+        for v in (0..F65537::P).step_by(13).map(F65537::new) {
+            assert_eq!(tonelli_shanks(&v), cipolla(&v));
+        }
+    }
+}
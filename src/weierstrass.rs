@@ -0,0 +1,1200 @@
+//! Elliptic curves in short Weierstrass form: y^2 = x^3 + a*x + b
+//!
+//! From: https://en.wikipedia.org/wiki/Elliptic_curve
+
+use crate::bigint::AsLimbs;
+use crate::curve::{montgomery_ladder, EllipticCurve, Point};
+use crate::ff::FiniteField;
+use std::marker::PhantomData;
+
+use bitvec::prelude::*;
+
+/// A short Weierstrass curve y^2 = x^3 + a*x + b over the finite field F
+/// with a base point (aka generator) of prime order n (and h = #E / n the cofactor)
+///
+/// Scalars are elements of S, the prime field 𝔽n (n is S::MODULUS)
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeierstrassCurve<F: FiniteField, S: FiniteField> {
+    a: F,
+    b: F,
+    base_point: Point<F>,
+    cofactor: u64,
+    /// a = -3 (as for the NIST curves): doubling can use 3x^2 + a = 3(x - 1)(x + 1)
+    a_is_minus_3: bool,
+    /// a = 0 (as for secp256k1): the complete formulas skip the multiplications by a
+    a_is_zero: bool,
+    /// 3 * b (used by the complete formulas)
+    b3: F,
+    scalar: PhantomData<S>,
+}
+
+/// A point in Jacobian projective coordinates: (X : Y : Z) is the affine point (X / Z^2, Y / Z^3)
+/// and any point with Z = 0 is the point at infinity
+///
+/// From: https://hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html
+///
+/// Additions & doublings don't need any inversion: only the conversion to affine does
+#[derive(Debug, Clone, Copy)]
+pub struct JacobianPoint<F: FiniteField> {
+    x: F,
+    y: F,
+    z: F,
+}
+
+impl<F: FiniteField> JacobianPoint<F> {
+    pub fn identity() -> Self {
+        Self {
+            x: F::one(),
+            y: F::one(),
+            z: F::min(),
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.z == F::min()
+    }
+
+    pub fn from_affine(p: &Point<F>) -> Self {
+        match p {
+            Point::Identity => Self::identity(),
+            Point::Affine(x, y) => Self {
+                x: *x,
+                y: *y,
+                z: F::one(),
+            },
+        }
+    }
+
+    pub fn to_affine(&self) -> Point<F> {
+        match self.z.mul_inverse() {
+            None => Point::Identity,
+            Some(z_inv) => {
+                let z_inv2 = z_inv.square();
+                Point::new(self.x * z_inv2, self.y * z_inv2 * z_inv)
+            }
+        }
+    }
+}
+
+impl<F: FiniteField> PartialEq for JacobianPoint<F> {
+    /// Same affine point: X1 * Z2^2 = X2 * Z1^2 & Y1 * Z2^3 = Y2 * Z1^3 (without inversion)
+    fn eq(&self, other: &Self) -> bool {
+        if self.is_identity() || other.is_identity() {
+            return self.is_identity() && other.is_identity();
+        }
+        let (z1z1, z2z2) = (self.z.square(), other.z.square());
+        self.x * z2z2 == other.x * z1z1 && self.y * z2z2 * other.z == other.y * z1z1 * self.z
+    }
+}
+
+/// A point in homogeneous projective coordinates: (X : Y : Z) is the affine point (X / Z, Y / Z)
+/// and the point at infinity is (0 : 1 : 0)
+#[derive(Debug, Clone, Copy)]
+pub struct ProjectivePoint<F: FiniteField> {
+    x: F,
+    y: F,
+    z: F,
+}
+
+impl<F: FiniteField> ProjectivePoint<F> {
+    pub fn identity() -> Self {
+        Self {
+            x: F::min(),
+            y: F::one(),
+            z: F::min(),
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.z == F::min()
+    }
+
+    pub fn from_affine(p: &Point<F>) -> Self {
+        match p {
+            Point::Identity => Self::identity(),
+            Point::Affine(x, y) => Self {
+                x: *x,
+                y: *y,
+                z: F::one(),
+            },
+        }
+    }
+
+    pub fn to_affine(&self) -> Point<F> {
+        match self.z.mul_inverse() {
+            None => Point::Identity,
+            Some(z_inv) => Point::new(self.x * z_inv, self.y * z_inv),
+        }
+    }
+}
+
+impl<F: FiniteField> PartialEq for ProjectivePoint<F> {
+    /// Same affine point: X1 * Z2 = X2 * Z1 & Y1 * Z2 = Y2 * Z1 (this also works for (0 : 1 : 0))
+    fn eq(&self, other: &Self) -> bool {
+        self.x * other.z == other.x * self.z && self.y * other.z == other.y * self.z
+    }
+}
+
+impl<F: FiniteField, S: FiniteField> WeierstrassCurve<F, S> {
+    pub fn try_new(a: F, b: F, base_point: Point<F>, cofactor: u64) -> Result<Self, &'static str> {
+        // The curve must be non singular (no cusp or self intersection): 4a^3 + 27b^2 != 0
+        let discriminant = F::from_u64(4) * a * a * a + F::from_u64(27) * b * b;
+        if discriminant == F::min() {
+            return Err("Curve is singular: 4a^3 + 27b^2 = 0");
+        }
+
+        let curve = Self::new_unchecked(a, b, base_point, cofactor);
+        if !curve.is_on_curve(&base_point) {
+            return Err("Base point is not on the curve");
+        }
+        // The order of the base point must be n = S::MODULUS (a prime): (n - 1) * G = -G
+        if base_point.is_identity() {
+            return Err("Base point is the identity");
+        }
+        if curve.point_mul(base_point, S::max()) != curve.point_neg(&base_point) {
+            return Err("Base point order is not the scalar field modulus");
+        }
+
+        Ok(curve)
+    }
+
+    pub fn new(a: F, b: F, base_point: Point<F>, cofactor: u64) -> Self {
+        Self::try_new(a, b, base_point, cofactor)
+            .unwrap_or_else(|e| panic!("Cannot init a WeierstrassCurve: {}", e))
+    }
+
+    /// No check at all (those of `try_new` cost a full scalar multiplication): only for the
+    /// standard curves of this crate, whose tests check the parameters with `try_new`
+    pub(crate) fn new_unchecked(a: F, b: F, base_point: Point<F>, cofactor: u64) -> Self {
+        Self {
+            a,
+            b,
+            base_point,
+            cofactor,
+            a_is_minus_3: a == -F::from_u64(3),
+            a_is_zero: a == F::min(),
+            b3: F::from_u64(3) * b,
+            scalar: PhantomData,
+        }
+    }
+
+    pub fn a(&self) -> F {
+        self.a
+    }
+
+    pub fn b(&self) -> F {
+        self.b
+    }
+
+    /// Order of the base point (little endian u64 limbs)
+    pub fn order(&self) -> &'static [u64] {
+        S::MODULUS
+    }
+
+    /// x^3 + a*x + b
+    fn rhs(&self, x: F) -> F {
+        x * x * x + self.a * x + self.b
+    }
+
+    /// Return true if the point satisfies the curve equation (the identity is on every curve)
+    pub fn is_on_curve(&self, p: &Point<F>) -> bool {
+        match p {
+            Point::Identity => true,
+            Point::Affine(x, y) => *y * *y == self.rhs(*x),
+        }
+    }
+
+    /// Encode a point as SEC1 bytes (From: https://www.secg.org/sec1-v2.pdf section 2.3.3)
+    ///
+    /// - the identity: 0x00
+    /// - compressed: 0x02 (y even) or 0x03 (y odd) || x
+    /// - uncompressed: 0x04 || x || y
+    pub fn to_sec1_bytes(&self, p: &Point<F>, compressed: bool) -> Vec<u8> {
+        match p {
+            Point::Identity => vec![0x00],
+            Point::Affine(x, y) if compressed => {
+                let mut bytes = vec![if y.is_odd() { 0x03 } else { 0x02 }];
+                bytes.extend(x.to_be_bytes());
+                bytes
+            }
+            Point::Affine(x, y) => {
+                let mut bytes = vec![0x04];
+                bytes.extend(x.to_be_bytes());
+                bytes.extend(y.to_be_bytes());
+                bytes
+            }
+        }
+    }
+
+    /// Decode SEC1 bytes (From: https://www.secg.org/sec1-v2.pdf section 2.3.4)
+    /// Return an error if the encoding is invalid or if the point is not on the curve
+    pub fn from_sec1_bytes(&self, bytes: &[u8]) -> Result<Point<F>, &'static str> {
+        let len = F::byte_len();
+
+        match bytes {
+            [0x00] => Ok(Point::Identity),
+            [prefix @ (0x02 | 0x03), x @ ..] if x.len() == len => {
+                let x = F::from_be_bytes(x).ok_or("Invalid x coordinate")?;
+                // Both roots have a different parity (y and p - y with p odd) unless y = 0
+                let (y1, y2) = self.eval_at(x).ok_or("Point is not on the curve")?;
+                let odd = *prefix == 0x03;
+                let y = if y1.is_odd() == odd { y1 } else { y2 };
+                if y.is_odd() != odd {
+                    return Err("Invalid y parity");
+                }
+                Ok(Point::new(x, y))
+            }
+            [0x04, xy @ ..] if xy.len() == 2 * len => {
+                let x = F::from_be_bytes(&xy[..len]).ok_or("Invalid x coordinate")?;
+                let y = F::from_be_bytes(&xy[len..]).ok_or("Invalid y coordinate")?;
+                let p = Point::new(x, y);
+                if self.is_on_curve(&p) {
+                    Ok(p)
+                } else {
+                    Err("Point is not on the curve")
+                }
+            }
+            _ => Err("Invalid SEC1 encoding"),
+        }
+    }
+
+    /// P + Q in projective coordinates using complete formulas: no special case at all,
+    /// the same operations are valid for P = Q, P = -Q or the identity
+    ///
+    /// From: https://eprint.iacr.org/2015/1060.pdf (Renes, Costello & Batina: algorithms 1, 4 & 7)
+    ///
+    /// Note: the formulas are complete only if the group has no point of order 2
+    /// (e.g. a curve of prime order): otherwise P + Q with P - Q of order 2 gives (0 : 0 : 0)
+    pub fn complete_add(
+        &self,
+        p: &ProjectivePoint<F>,
+        q: &ProjectivePoint<F>,
+    ) -> ProjectivePoint<F> {
+        if self.a_is_zero {
+            self.complete_add_a_zero(p, q)
+        } else if self.a_is_minus_3 {
+            self.complete_add_a_minus_3(p, q)
+        } else {
+            self.complete_add_generic(p, q)
+        }
+    }
+
+    /// 2 * P in projective coordinates using complete formulas (valid for any point)
+    ///
+    /// From: https://eprint.iacr.org/2015/1060.pdf (Renes, Costello & Batina: algorithms 3, 6 & 9)
+    pub fn complete_double(&self, p: &ProjectivePoint<F>) -> ProjectivePoint<F> {
+        if self.a_is_zero {
+            self.complete_double_a_zero(p)
+        } else if self.a_is_minus_3 {
+            self.complete_double_a_minus_3(p)
+        } else {
+            self.complete_double_generic(p)
+        }
+    }
+
+    /// Algorithm 1 (any a)
+    fn complete_add_generic(
+        &self,
+        p: &ProjectivePoint<F>,
+        q: &ProjectivePoint<F>,
+    ) -> ProjectivePoint<F> {
+        let (x1, y1, z1) = (p.x, p.y, p.z);
+        let (x2, y2, z2) = (q.x, q.y, q.z);
+        let (a, b3) = (self.a, self.b3);
+
+        let mut t0 = x1 * x2;
+        let mut t1 = y1 * y2;
+        let mut t2 = z1 * z2;
+        let mut t3 = x1 + y1;
+        let mut t4 = x2 + y2;
+        t3 = t3 * t4;
+        t4 = t0 + t1;
+        t3 = t3 - t4;
+        t4 = x1 + z1;
+        let mut t5 = x2 + z2;
+        t4 = t4 * t5;
+        t5 = t0 + t2;
+        t4 = t4 - t5;
+        t5 = y1 + z1;
+        let mut x3 = y2 + z2;
+        t5 = t5 * x3;
+        x3 = t1 + t2;
+        t5 = t5 - x3;
+        let mut z3 = a * t4;
+        x3 = b3 * t2;
+        z3 = x3 + z3;
+        x3 = t1 - z3;
+        z3 = t1 + z3;
+        let mut y3 = x3 * z3;
+        t1 = t0 + t0;
+        t1 = t1 + t0;
+        t2 = a * t2;
+        t4 = b3 * t4;
+        t1 = t1 + t2;
+        t2 = t0 - t2;
+        t2 = a * t2;
+        t4 = t4 + t2;
+        t0 = t1 * t4;
+        y3 = y3 + t0;
+        t0 = t5 * t4;
+        x3 = t3 * x3;
+        x3 = x3 - t0;
+        t0 = t3 * t1;
+        z3 = t5 * z3;
+        z3 = z3 + t0;
+
+        ProjectivePoint {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    /// Algorithm 3 (any a)
+    fn complete_double_generic(&self, p: &ProjectivePoint<F>) -> ProjectivePoint<F> {
+        let (x, y, z) = (p.x, p.y, p.z);
+        let (a, b3) = (self.a, self.b3);
+
+        let mut t0 = x * x;
+        let t1 = y * y;
+        let mut t2 = z * z;
+        let mut t3 = x * y;
+        t3 = t3 + t3;
+        let mut z3 = x * z;
+        z3 = z3 + z3;
+        let mut x3 = a * z3;
+        let mut y3 = b3 * t2;
+        y3 = x3 + y3;
+        x3 = t1 - y3;
+        y3 = t1 + y3;
+        y3 = x3 * y3;
+        x3 = t3 * x3;
+        z3 = b3 * z3;
+        t2 = a * t2;
+        t3 = t0 - t2;
+        t3 = a * t3;
+        t3 = t3 + z3;
+        z3 = t0 + t0;
+        t0 = z3 + t0;
+        t0 = t0 + t2;
+        t0 = t0 * t3;
+        y3 = y3 + t0;
+        t2 = y * z;
+        t2 = t2 + t2;
+        t0 = t2 * t3;
+        x3 = x3 - t0;
+        z3 = t2 * t1;
+        z3 = z3 + z3;
+        z3 = z3 + z3;
+
+        ProjectivePoint {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    /// Algorithm 4 (a = -3)
+    fn complete_add_a_minus_3(
+        &self,
+        p: &ProjectivePoint<F>,
+        q: &ProjectivePoint<F>,
+    ) -> ProjectivePoint<F> {
+        let (x1, y1, z1) = (p.x, p.y, p.z);
+        let (x2, y2, z2) = (q.x, q.y, q.z);
+        let b = self.b;
+
+        let mut t0 = x1 * x2;
+        let mut t1 = y1 * y2;
+        let mut t2 = z1 * z2;
+        let mut t3 = x1 + y1;
+        let mut t4 = x2 + y2;
+        t3 = t3 * t4;
+        t4 = t0 + t1;
+        t3 = t3 - t4;
+        t4 = y1 + z1;
+        let mut x3 = y2 + z2;
+        t4 = t4 * x3;
+        x3 = t1 + t2;
+        t4 = t4 - x3;
+        x3 = x1 + z1;
+        let mut y3 = x2 + z2;
+        x3 = x3 * y3;
+        y3 = t0 + t2;
+        y3 = x3 - y3;
+        let mut z3 = b * t2;
+        x3 = y3 - z3;
+        z3 = x3 + x3;
+        x3 = x3 + z3;
+        z3 = t1 - x3;
+        x3 = t1 + x3;
+        y3 = b * y3;
+        t1 = t2 + t2;
+        t2 = t1 + t2;
+        y3 = y3 - t2;
+        y3 = y3 - t0;
+        t1 = y3 + y3;
+        y3 = t1 + y3;
+        t1 = t0 + t0;
+        t0 = t1 + t0;
+        t0 = t0 - t2;
+        t1 = t4 * y3;
+        t2 = t0 * y3;
+        y3 = x3 * z3;
+        y3 = y3 + t2;
+        x3 = t3 * x3;
+        x3 = x3 - t1;
+        z3 = t4 * z3;
+        t1 = t3 * t0;
+        z3 = z3 + t1;
+
+        ProjectivePoint {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    /// Algorithm 6 (a = -3)
+    fn complete_double_a_minus_3(&self, p: &ProjectivePoint<F>) -> ProjectivePoint<F> {
+        let (x, y, z) = (p.x, p.y, p.z);
+        let b = self.b;
+
+        let mut t0 = x * x;
+        let t1 = y * y;
+        let mut t2 = z * z;
+        let mut t3 = x * y;
+        t3 = t3 + t3;
+        let mut z3 = x * z;
+        z3 = z3 + z3;
+        let mut y3 = b * t2;
+        y3 = y3 - z3;
+        let mut x3 = y3 + y3;
+        y3 = x3 + y3;
+        x3 = t1 - y3;
+        y3 = t1 + y3;
+        y3 = x3 * y3;
+        x3 = x3 * t3;
+        t3 = t2 + t2;
+        t2 = t2 + t3;
+        z3 = b * z3;
+        z3 = z3 - t2;
+        z3 = z3 - t0;
+        t3 = z3 + z3;
+        z3 = z3 + t3;
+        t3 = t0 + t0;
+        t0 = t3 + t0;
+        t0 = t0 - t2;
+        t0 = t0 * z3;
+        y3 = y3 + t0;
+        t0 = y * z;
+        t0 = t0 + t0;
+        z3 = t0 * z3;
+        x3 = x3 - z3;
+        z3 = t0 * t1;
+        z3 = z3 + z3;
+        z3 = z3 + z3;
+
+        ProjectivePoint {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    /// Algorithm 7 (a = 0)
+    fn complete_add_a_zero(
+        &self,
+        p: &ProjectivePoint<F>,
+        q: &ProjectivePoint<F>,
+    ) -> ProjectivePoint<F> {
+        let (x1, y1, z1) = (p.x, p.y, p.z);
+        let (x2, y2, z2) = (q.x, q.y, q.z);
+        let b3 = self.b3;
+
+        let mut t0 = x1 * x2;
+        let mut t1 = y1 * y2;
+        let mut t2 = z1 * z2;
+        let mut t3 = x1 + y1;
+        let mut t4 = x2 + y2;
+        t3 = t3 * t4;
+        t4 = t0 + t1;
+        t3 = t3 - t4;
+        t4 = y1 + z1;
+        let mut x3 = y2 + z2;
+        t4 = t4 * x3;
+        x3 = t1 + t2;
+        t4 = t4 - x3;
+        x3 = x1 + z1;
+        let mut y3 = x2 + z2;
+        x3 = x3 * y3;
+        y3 = t0 + t2;
+        y3 = x3 - y3;
+        x3 = t0 + t0;
+        t0 = x3 + t0;
+        t2 = b3 * t2;
+        let mut z3 = t1 + t2;
+        t1 = t1 - t2;
+        y3 = b3 * y3;
+        x3 = t4 * y3;
+        t2 = t3 * t1;
+        x3 = t2 - x3;
+        y3 = y3 * t0;
+        t1 = t1 * z3;
+        y3 = t1 + y3;
+        t0 = t0 * t3;
+        z3 = z3 * t4;
+        z3 = z3 + t0;
+
+        ProjectivePoint {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    /// Algorithm 9 (a = 0)
+    fn complete_double_a_zero(&self, p: &ProjectivePoint<F>) -> ProjectivePoint<F> {
+        let (x, y, z) = (p.x, p.y, p.z);
+        let b3 = self.b3;
+
+        let mut t0 = y * y;
+        let mut z3 = t0 + t0;
+        z3 = z3 + z3;
+        z3 = z3 + z3;
+        let mut t1 = y * z;
+        let mut t2 = z * z;
+        t2 = b3 * t2;
+        let mut x3 = t2 * z3;
+        let mut y3 = t0 + t2;
+        z3 = t1 * z3;
+        t1 = t2 + t2;
+        t2 = t1 + t2;
+        t0 = t0 - t2;
+        y3 = t0 * y3;
+        y3 = x3 + y3;
+        t1 = x * y;
+        x3 = t0 * t1;
+        x3 = x3 + x3;
+
+        ProjectivePoint {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    /// 2 * P in Jacobian coordinates
+    ///
+    /// From: https://hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html#doubling-dbl-2007-bl
+    /// (and #doubling-dbl-2001-b for a = -3)
+    ///
+    /// Note: a point with Y = 0 (or the identity) gives Z3 = 2 * Y * Z = 0 (the identity)
+    pub fn jacobian_double(&self, p: &JacobianPoint<F>) -> JacobianPoint<F> {
+        let two = F::from_u64(2);
+        let three = F::from_u64(3);
+        let eight = F::from_u64(8);
+
+        if self.a_is_minus_3 {
+            let delta = p.z.square();
+            let gamma = p.y.square();
+            let beta = p.x * gamma;
+            let alpha = three * (p.x - delta) * (p.x + delta);
+            let x3 = alpha.square() - eight * beta;
+            let z3 = (p.y + p.z).square() - gamma - delta;
+            let y3 = alpha * (F::from_u64(4) * beta - x3) - eight * gamma.square();
+            return JacobianPoint {
+                x: x3,
+                y: y3,
+                z: z3,
+            };
+        }
+
+        let xx = p.x.square();
+        let yy = p.y.square();
+        let yyyy = yy.square();
+        let zz = p.z.square();
+        let s = two * ((p.x + yy).square() - xx - yyyy);
+        let m = three * xx + self.a * zz.square();
+        let t = m.square() - two * s;
+        JacobianPoint {
+            x: t,
+            y: m * (s - t) - eight * yyyy,
+            z: (p.y + p.z).square() - yy - zz,
+        }
+    }
+
+    /// P + Q in Jacobian coordinates
+    ///
+    /// From: https://hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html#addition-add-2007-bl
+    pub fn jacobian_add(&self, p: &JacobianPoint<F>, q: &JacobianPoint<F>) -> JacobianPoint<F> {
+        if p.is_identity() {
+            return *q;
+        }
+        if q.is_identity() {
+            return *p;
+        }
+
+        let z1z1 = p.z.square();
+        let z2z2 = q.z.square();
+        let u1 = p.x * z2z2;
+        let u2 = q.x * z1z1;
+        let s1 = p.y * q.z * z2z2;
+        let s2 = q.y * p.z * z1z1;
+        let h = u2 - u1;
+        let r = F::from_u64(2) * (s2 - s1);
+
+        // Same x: P = Q (the formula is not valid for a doubling) or P = -Q
+        if h == F::min() {
+            return match r == F::min() {
+                true => self.jacobian_double(p),
+                false => JacobianPoint::identity(),
+            };
+        }
+
+        let i = (h + h).square();
+        let j = h * i;
+        let v = u1 * i;
+        let x3 = r.square() - j - v - v;
+        JacobianPoint {
+            x: x3,
+            y: r * (v - x3) - F::from_u64(2) * s1 * j,
+            z: ((p.z + q.z).square() - z1z1 - z2z2) * h,
+        }
+    }
+
+    /// P + Q in Jacobian coordinates with Q = (qx, qy) an affine point (Z2 = 1: "mixed" addition)
+    ///
+    /// From: https://hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html#addition-madd-2007-bl
+    pub fn jacobian_add_mixed(&self, p: &JacobianPoint<F>, qx: F, qy: F) -> JacobianPoint<F> {
+        if p.is_identity() {
+            return JacobianPoint::from_affine(&Point::new(qx, qy));
+        }
+
+        let z1z1 = p.z.square();
+        let u2 = qx * z1z1;
+        let s2 = qy * p.z * z1z1;
+        let h = u2 - p.x;
+        let r = F::from_u64(2) * (s2 - p.y);
+
+        // Same x: P = Q (the formula is not valid for a doubling) or P = -Q
+        if h == F::min() {
+            return match r == F::min() {
+                true => self.jacobian_double(p),
+                false => JacobianPoint::identity(),
+            };
+        }
+
+        let hh = h.square();
+        let i = F::from_u64(4) * hh;
+        let j = h * i;
+        let v = p.x * i;
+        let x3 = r.square() - j - v - v;
+        JacobianPoint {
+            x: x3,
+            y: r * (v - x3) - F::from_u64(2) * p.y * j,
+            z: (p.z + h).square() - z1z1 - hh,
+        }
+    }
+
+    /// s1 * P1 + s2 * P2 + ... + sk * Pk (aka multi-scalar multiplication)
+    ///
+    /// Straus's algorithm (aka Shamir's trick): a single double-and-add over the bits of all the
+    /// scalars at once, so the doublings are shared by the k terms (about 2x faster than k
+    /// separate `point_mul` for 256 bits scalars)
+    pub fn multi_mul(&self, terms: &[(Point<F>, S)]) -> Point<F> {
+        let modulus_bits = S::MODULUS.view_bits::<Lsb0>();
+        let len = modulus_bits.last_one().map_or(0, |i| i + 1);
+
+        // O * s = O: only the affine points are added
+        let terms: Vec<_> = terms
+            .iter()
+            .filter_map(|(p, s)| p.coordinates().map(|(x, y)| (x, y, s.value())))
+            .collect();
+
+        let mut res = JacobianPoint::identity();
+        for i in (0..len).rev() {
+            res = self.jacobian_double(&res);
+            for (px, py, num) in &terms {
+                if num.as_limbs().view_bits::<Lsb0>()[i] {
+                    res = self.jacobian_add_mixed(&res, *px, *py);
+                }
+            }
+        }
+
+        res.to_affine()
+    }
+}
+
+impl<F: FiniteField, S: FiniteField> EllipticCurve for WeierstrassCurve<F, S> {
+    type ValueType = F;
+    type ScalarType = S;
+
+    fn base_point(&self) -> Point<F> {
+        self.base_point
+    }
+
+    fn eval_at(&self, x: F) -> Option<(F, F)> {
+        self.rhs(x).square_root()
+    }
+
+    fn point_add(&self, p: &Point<F>, q: &Point<F>) -> Point<F> {
+        // From: https://en.wikipedia.org/wiki/Elliptic_curve_point_multiplication#Point_addition
+
+        let ((px, py), (qx, qy)) = match (p, q) {
+            // O + Q = Q & P + O = P (and O + O = O)
+            (Point::Identity, _) => return *q,
+            (_, Point::Identity) => return *p,
+            (Point::Affine(px, py), Point::Affine(qx, qy)) => ((*px, *py), (*qx, *qy)),
+        };
+
+        // P + (-P) = O (this includes doubling a point with y = 0: the tangent is vertical)
+        if px == qx && py == -qy {
+            return Point::Identity;
+        }
+
+        if p == q {
+            return self.point_double(p);
+        }
+
+        let num = qy - py;
+        let denom = qx - px;
+        // Safe to unwrap: px != qx (px == qx implies q == p or q == -p)
+        let lambda = num * denom.mul_inverse().unwrap();
+
+        let x3 = lambda * lambda - px - qx;
+        let y3 = lambda * (px - x3) - py;
+
+        Point::new(x3, y3)
+    }
+
+    fn point_neg(&self, p: &Point<F>) -> Point<F> {
+        // P = (x, y) then -P = (x, -y): the reflection over the x-axis
+        match p {
+            Point::Identity => Point::Identity,
+            Point::Affine(x, y) => Point::new(*x, -*y),
+        }
+    }
+
+    fn point_double(&self, p: &Point<F>) -> Point<F> {
+        let (px, py) = match p {
+            Point::Identity => return Point::Identity,
+            Point::Affine(px, py) => (*px, *py),
+        };
+
+        // The tangent is vertical for y = 0
+        if py == F::min() {
+            return Point::Identity;
+        }
+
+        let num = if self.a_is_minus_3 {
+            // 3x^2 - 3 = 3(x - 1)(x + 1): one multiplication instead of a squaring and an addition
+            F::from_u64(3) * (px - F::one()) * (px + F::one())
+        } else {
+            F::from_u64(3) * px.square() + self.a
+        };
+        let denom = F::from_u64(2) * py;
+        // Safe to unwrap: py != 0 (handled above)
+        let lambda = num * denom.mul_inverse().unwrap();
+
+        let x3 = lambda.square() - px - px;
+        let y3 = lambda * (px - x3) - py;
+
+        Point::new(x3, y3)
+    }
+
+    fn point_mul(&self, p: Point<F>, s: S) -> Point<F> {
+        // From: https://en.wikipedia.org/wiki/Elliptic_curve_point_multiplication#Double-and-add
+        // (from the most significant bit, in Jacobian coordinates: a single inversion at the end)
+
+        let (px, py) = match p {
+            Point::Identity => return Point::Identity,
+            Point::Affine(px, py) => (px, py),
+        };
+
+        let num = s.value();
+        let bits = num.as_limbs().view_bits::<Lsb0>();
+
+        let mut res = JacobianPoint::identity();
+
+        for bit in bits[..bits.last_one().map_or(0, |i| i + 1)].iter().rev() {
+            res = self.jacobian_double(&res);
+            if *bit {
+                res = self.jacobian_add_mixed(&res, px, py);
+            }
+        }
+
+        res.to_affine()
+    }
+
+    /// Montgomery ladder in projective coordinates with the complete formulas
+    ///
+    /// Their only exceptional case is P + Q with P - Q of order 2, but r1 - r0 = P at every
+    /// step of the ladder: a point P of order 2 (y = 0) is the only one handled separately
+    fn point_mul_ladder(&self, p: Point<F>, s: S) -> Point<F> {
+        if let Point::Affine(_, y) = p {
+            if y == F::min() {
+                // k * P = P for an odd k, O otherwise
+                return if s.is_odd() { p } else { Point::Identity };
+            }
+        }
+
+        montgomery_ladder(
+            s,
+            ProjectivePoint::from_affine(&p),
+            ProjectivePoint::identity(),
+            |p, q| self.complete_add(p, q),
+            |p| self.complete_double(p),
+        )
+        .to_affine()
+    }
+
+    /// The cofactor given to `new` (no enumeration)
+    fn cofactor(&self) -> u64 {
+        self.cofactor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ff::Fp;
+
+    type F23 = Fp<23>;
+    type S2 = Fp<2>;
+
+    /// y^2 = x^3 + x over 𝔽23 ((0, 0) is a point of order 2)
+    fn curve() -> WeierstrassCurve<F23, S2> {
+        WeierstrassCurve::new(
+            F23::new(1),
+            F23::new(0),
+            Point::new(F23::new(0), F23::new(0)),
+            12,
+        )
+    }
+
+    #[test]
+    fn test_try_new() {
+        // 4 * 0^3 + 27 * 0^2 = 0 (cusp)
+        assert!(
+            WeierstrassCurve::<F23, S2>::try_new(F23::new(0), F23::new(0), Point::Identity, 1)
+                .is_err()
+        );
+        // 4 * (-3)^3 + 27 * 2^2 = 0 (self intersection)
+        assert!(WeierstrassCurve::<F23, S2>::try_new(
+            -F23::new(3),
+            F23::new(2),
+            Point::Identity,
+            1
+        )
+        .is_err());
+        // (1, 1) is not on y^2 = x^3 + x
+        assert!(WeierstrassCurve::<F23, S2>::try_new(
+            F23::new(1),
+            F23::new(0),
+            Point::new(F23::new(1), F23::new(1)),
+            12
+        )
+        .is_err());
+        // The base point must have the order 2 (the modulus of S2): not O & not (1, 5) (order 4)
+        assert!(WeierstrassCurve::<F23, S2>::try_new(
+            F23::new(1),
+            F23::new(0),
+            Point::Identity,
+            12
+        )
+        .is_err());
+        assert!(WeierstrassCurve::<F23, S2>::try_new(
+            F23::new(1),
+            F23::new(0),
+            Point::new(F23::new(1), F23::new(5)),
+            12
+        )
+        .is_err());
+        assert!(WeierstrassCurve::<F23, S2>::try_new(
+            F23::new(1),
+            F23::new(0),
+            curve().base_point(),
+            12
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_point_add() {
+        let c = curve();
+
+        // Doubling a point with y = 0 gives the identity
+        let p = c.base_point();
+        assert_eq!(c.point_add(&p, &p), Point::Identity);
+        assert_eq!(c.point_mul(p, S2::new(1)), p);
+        // Scalars are reduced mod the order of the base point
+        assert_eq!(c.order(), &[2]);
+        assert_eq!(c.point_mul(p, S2::from_u64(2)), Point::Identity);
+        assert_eq!(c.point_mul(p, S2::from_u64(3)), p);
+
+        // All points stay on the curve
+        for x in 0..23 {
+            if let Some((y, _)) = c.eval_at(F23::new(x)) {
+                let q = Point::new(F23::new(x), y);
+                assert!(c.is_on_curve(&q));
+                assert!(c.is_on_curve(&c.point_add(&q, &q)));
+                assert!(c.is_on_curve(&c.point_add(&q, &p)));
+                assert_eq!(c.point_add(&q, &c.point_neg(&q)), Point::Identity);
+            }
+        }
+    }
+
+    #[test]
+    fn test_order() {
+        // 24 points: a cyclic group (8 generators) & the base point (0, 0) of order 2 (h = 12)
+        let c = curve();
+        assert_eq!(c.group_order(), 24);
+        assert_eq!(c.point_order(&c.base_point()), 2);
+        assert_eq!(c.cofactor(), 12);
+        assert_eq!(
+            c.cofactor(),
+            c.group_order() / c.point_order(&c.base_point())
+        );
+
+        let points: Vec<_> = c.points().collect();
+        assert_eq!(points.len(), 24);
+
+        // (order, number of points) computed with python
+        let expected = [
+            (1, 1),
+            (2, 1),
+            (3, 2),
+            (4, 2),
+            (6, 2),
+            (8, 4),
+            (12, 4),
+            (24, 8),
+        ];
+        for (order, count) in expected {
+            let n = points.iter().filter(|p| c.point_order(p) == order).count();
+            assert_eq!(n, count);
+        }
+    }
+
+    /// Compare the Jacobian formulas with the affine ones for every pair of points
+    fn check_jacobian<S: FiniteField>(c: &WeierstrassCurve<F23, S>) {
+        let points: Vec<_> = c.points().collect();
+
+        // Scale (X, Y, Z) by (λ^2, λ^3, λ): another representation of the same point
+        let scaled = |p: &Point<F23>| {
+            let mut j = JacobianPoint::from_affine(p);
+            let l = F23::new(5);
+            j.x = j.x * l * l;
+            j.y = j.y * l * l * l;
+            j.z = j.z * l;
+            j
+        };
+
+        for p in &points {
+            let jp = scaled(p);
+            assert_eq!(jp, JacobianPoint::from_affine(p));
+            assert_eq!(jp.to_affine(), *p);
+            assert_eq!(c.jacobian_double(&jp).to_affine(), c.point_double(p));
+            for q in &points {
+                let expected = c.point_add(p, q);
+                assert_eq!(c.jacobian_add(&jp, &scaled(q)).to_affine(), expected);
+                if let Some((qx, qy)) = q.coordinates() {
+                    assert_eq!(c.jacobian_add_mixed(&jp, qx, qy).to_affine(), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_jacobian() {
+        check_jacobian(&curve());
+        // a = -3: y^2 = x^3 - 3x + 1
+        let c = WeierstrassCurve::<F23, Fp<23>>::new(
+            -F23::new(3),
+            F23::new(1),
+            Point::new(F23::new(0), F23::new(1)),
+            1,
+        );
+        assert!(c.a_is_minus_3);
+        check_jacobian(&c);
+    }
+
+    /// Compare the complete formulas with the affine ones for every pair of points
+    /// (the curve must have an odd order)
+    fn check_complete<F: FiniteField, S: FiniteField>(c: &WeierstrassCurve<F, S>) {
+        let points: Vec<_> = c.points().collect();
+
+        // Scale (X, Y, Z) by λ: another representation of the same point
+        let scaled = |p: &Point<F>| {
+            let mut pp = ProjectivePoint::from_affine(p);
+            let l = F::from_u64(7);
+            (pp.x, pp.y, pp.z) = (pp.x * l, pp.y * l, pp.z * l);
+            pp
+        };
+
+        for p in &points {
+            let pp = scaled(p);
+            assert_eq!(pp, ProjectivePoint::from_affine(p));
+            assert_eq!(pp.to_affine(), *p);
+            assert_eq!(c.complete_double(&pp).to_affine(), c.point_double(p));
+            for q in &points {
+                assert_eq!(
+                    c.complete_add(&pp, &scaled(q)).to_affine(),
+                    c.point_add(p, q)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_complete() {
+        // Any a: y^2 = x^3 + x + 4 over 𝔽23 (order 29, the base point (0, 2) as Curve23)
+        let c = WeierstrassCurve::<F23, Fp<29>>::new(
+            F23::new(1),
+            F23::new(4),
+            Point::new(F23::new(0), F23::new(2)),
+            1,
+        );
+        assert!(!c.a_is_zero && !c.a_is_minus_3);
+        check_complete(&c);
+
+        // a = -3: y^2 = x^3 - 3x + 1 over 𝔽23 (order 23)
+        let c = WeierstrassCurve::<F23, Fp<23>>::new(
+            -F23::new(3),
+            F23::new(1),
+            Point::new(F23::new(0), F23::new(1)),
+            1,
+        );
+        assert!(c.a_is_minus_3);
+        check_complete(&c);
+
+        // a = 0: y^2 = x^3 + 3 over 𝔽31 (order 43)
+        type F31 = Fp<31>;
+        let c = WeierstrassCurve::<F31, Fp<43>>::new(
+            F31::new(0),
+            F31::new(3),
+            Point::new(F31::new(1), F31::new(2)),
+            1,
+        );
+        assert!(c.a_is_zero);
+        check_complete(&c);
+    }
+
+    #[test]
+    fn test_multi_mul() {
+        // y^2 = x^3 + x + 4 over 𝔽23 (order 29): compare against separate multiplications
+        type S29 = Fp<29>;
+        let c = WeierstrassCurve::<F23, S29>::new(
+            F23::new(1),
+            F23::new(4),
+            Point::new(F23::new(0), F23::new(2)),
+            1,
+        );
+        let g = c.base_point();
+        let p = c.point_mul(g, S29::new(5));
+        let q = c.point_mul(g, S29::new(17));
+
+        assert_eq!(c.multi_mul(&[]), Point::Identity);
+        for (s1, s2, s3) in [(0, 0, 0), (1, 2, 3), (28, 11, 4), (7, 0, 21), (13, 13, 13)] {
+            let (s1, s2, s3) = (S29::new(s1), S29::new(s2), S29::new(s3));
+            let expected = c.point_add(
+                &c.point_add(&c.point_mul(g, s1), &c.point_mul(p, s2)),
+                &c.point_mul(q, s3),
+            );
+            assert_eq!(c.multi_mul(&[(g, s1), (p, s2), (q, s3)]), expected);
+        }
+
+        // Same points (doublings), opposite points (cancel out) & the identity
+        assert_eq!(
+            c.multi_mul(&[(g, S29::new(3)), (g, S29::new(4))]),
+            c.point_mul(g, S29::new(7))
+        );
+        assert_eq!(
+            c.multi_mul(&[(p, S29::new(9)), (c.point_neg(&p), S29::new(9))]),
+            Point::Identity
+        );
+        assert_eq!(
+            c.multi_mul(&[(Point::Identity, S29::new(3)), (q, S29::new(2))]),
+            c.point_mul(q, S29::new(2))
+        );
+    }
+
+    /// Compare the ladder with `point_mul` for every point & every scalar
+    fn check_ladder<F: FiniteField, S: FiniteField>(c: &WeierstrassCurve<F, S>) {
+        for p in c.points() {
+            for k in 0..S::MODULUS[0] {
+                let k = S::from_u64(k);
+                assert_eq!(c.point_mul_ladder(p, k), c.point_mul(p, k));
+            }
+        }
+    }
+
+    #[test]
+    fn test_point_mul_ladder() {
+        // With points of order 2: y^2 = x^3 + x over 𝔽23 (24 points), the scalars mod 31
+        // (no base point order to check)
+        let c = curve();
+        check_ladder(&WeierstrassCurve::<F23, Fp<31>>::new_unchecked(
+            c.a,
+            c.b,
+            c.base_point,
+            12,
+        ));
+
+        // Odd orders (any a, a = -3 & a = 0), as in test_complete
+        check_ladder(&WeierstrassCurve::<F23, Fp<29>>::new(
+            F23::new(1),
+            F23::new(4),
+            Point::new(F23::new(0), F23::new(2)),
+            1,
+        ));
+        check_ladder(&WeierstrassCurve::<F23, Fp<23>>::new(
+            -F23::new(3),
+            F23::new(1),
+            Point::new(F23::new(0), F23::new(1)),
+            1,
+        ));
+        type F31 = Fp<31>;
+        check_ladder(&WeierstrassCurve::<F31, Fp<43>>::new(
+            F31::new(0),
+            F31::new(3),
+            Point::new(F31::new(1), F31::new(2)),
+            1,
+        ));
+    }
+
+    #[test]
+    fn test_sec1() {
+        let c = curve();
+
+        assert_eq!(c.to_sec1_bytes(&Point::Identity, true), vec![0x00]);
+        assert_eq!(c.from_sec1_bytes(&[0x00]), Ok(Point::Identity));
+
+        // Round trip for every point
+        for x in 0..23 {
+            if let Some((y1, y2)) = c.eval_at(F23::new(x)) {
+                for y in [y1, y2] {
+                    let p = Point::new(F23::new(x), y);
+                    for compressed in [true, false] {
+                        let bytes = c.to_sec1_bytes(&p, compressed);
+                        assert_eq!(bytes.len(), if compressed { 2 } else { 3 });
+                        assert_eq!(c.from_sec1_bytes(&bytes), Ok(p));
+                    }
+                }
+            }
+        }
+
+        // (0, 0): y = 0 is even
+        assert_eq!(c.to_sec1_bytes(&c.base_point(), true), vec![0x02, 0]);
+        assert!(c.from_sec1_bytes(&[0x03, 0]).is_err());
+        // (1, 1) is not on the curve, 23 is not a valid coordinate
+        assert!(c.from_sec1_bytes(&[0x04, 1, 1]).is_err());
+        assert!(c.from_sec1_bytes(&[0x02, 23]).is_err());
+        // Wrong lengths & prefixes
+        assert!(c.from_sec1_bytes(&[]).is_err());
+        assert!(c.from_sec1_bytes(&[0x02, 0, 0]).is_err());
+        assert!(c.from_sec1_bytes(&[0x04, 0]).is_err());
+        assert!(c.from_sec1_bytes(&[0x05, 0]).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+//! X25519 Diffie-Hellman function (RFC 7748)
+//!
+//! From: https://www.rfc-editor.org/rfc/rfc7748 & https://x25519.xargs.org/
+//!
+//! Curve25519 is the Montgomery curve y^2 = x^3 + 486662x^2 + x over 𝔽p (p = 2^255 - 19)
+
+use crate::ff::FiniteField;
+use crate::ff25519::FiniteField25519Point;
+use crate::montgomery::MontgomeryCurve;
+
+/// u-coordinate of the Curve25519 base point: u = 9 (little endian)
+pub const BASE_POINT: [u8; 32] = {
+    let mut u = [0; 32];
+    u[0] = 9;
+    u
+};
+
+/// Curve25519: y^2 = x^3 + 486662x^2 + x
+pub fn curve25519() -> MontgomeryCurve<FiniteField25519Point> {
+    MontgomeryCurve::new(FiniteField25519Point::from_u64(486662))
+}
+
+/// Clamp a 32 bytes scalar (RFC 7748 decodeScalar25519):
+/// clear the 3 lowest bits (multiple of the cofactor 8), clear bit 255 & set bit 254
+pub fn clamp_scalar(k: &[u8; 32]) -> [u8; 32] {
+    let mut k = *k;
+    k[0] &= 248;
+    k[31] &= 127;
+    k[31] |= 64;
+    k
+}
+
+/// X25519(k, u): the u-coordinate of k * P (with u = u(P)), all little endian 32 bytes
+pub fn x25519(k: &[u8; 32], u: &[u8; 32]) -> [u8; 32] {
+    let k = clamp_scalar(k);
+    // Note: from_bytes ignores the highest bit of u (as required by RFC 7748)
+    let u = FiniteField25519Point::from_bytes(u);
+
+    curve25519().ladder(&k, 255, u).to_bytes()
+}
+
+/// Public key from a private key: X25519(k, 9)
+pub fn public_key(k: &[u8; 32]) -> [u8; 32] {
+    x25519(k, &BASE_POINT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_hex(s: &str) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).unwrap();
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_x25519() {
+        // From: RFC 7748 section 5.2
+        let k = from_hex("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4");
+        let u = from_hex("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c");
+        assert_eq!(
+            x25519(&k, &u),
+            from_hex("c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552")
+        );
+
+        let k = from_hex("4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d");
+        let u = from_hex("e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493");
+        assert_eq!(
+            x25519(&k, &u),
+            from_hex("95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957")
+        );
+
+        // The highest bit of u is ignored
+        let mut u_high = u;
+        u_high[31] |= 0x80;
+        assert_eq!(x25519(&k, &u_high), x25519(&k, &u));
+    }
+
+    /// k = u = 9 then repeat: k, u = X25519(k, u), k
+    fn iterate(count: usize) -> [u8; 32] {
+        let mut k = BASE_POINT;
+        let mut u = BASE_POINT;
+        for _ in 0..count {
+            (k, u) = (x25519(&k, &u), k);
+        }
+        k
+    }
+
+    #[test]
+    fn test_x25519_iterations() {
+        // From: RFC 7748 section 5.2
+        assert_eq!(
+            iterate(1),
+            from_hex("422c8e7a6227d7bca1350b3e2bb7279f7897b87bb6854b783c60e80311ae3079")
+        );
+        assert_eq!(
+            iterate(1_000),
+            from_hex("684cf59ba83309552800ef566f2f4d3c1c3887c49360e3875f2eb94d99532c51")
+        );
+    }
+
+    #[test]
+    #[ignore = "slow: run with cargo test -- --ignored"]
+    fn test_x25519_iterations_1m() {
+        // From: RFC 7748 section 5.2
+        assert_eq!(
+            iterate(1_000_000),
+            from_hex("7c3911e0ab2586fd864497297e575e6f3bc601c0883c30df5f4dd2d24f665424")
+        );
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_key_exchange() {
+        // From: RFC 7748 section 6.1
+        let kA = from_hex("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+        let A = public_key(&kA);
+        assert_eq!(
+            A,
+            from_hex("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")
+        );
+
+        let kB = from_hex("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+        let B = public_key(&kB);
+        assert_eq!(
+            B,
+            from_hex("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+        );
+
+        let kA_B = x25519(&kA, &B);
+        let kB_A = x25519(&kB, &A);
+        assert_eq!(kA_B, kB_A);
+        assert_eq!(
+            kA_B,
+            from_hex("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742")
+        );
+    }
+}
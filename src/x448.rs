@@ -0,0 +1,140 @@
+//! X448 Diffie-Hellman function (RFC 7748)
+//!
+//! From: https://www.rfc-editor.org/rfc/rfc7748
+//!
+//! Curve448 is the Montgomery curve y^2 = x^3 + 156326x^2 + x over 𝔽p (p = 2^448 - 2^224 - 1)
+
+use crate::ff::FiniteField;
+use crate::ff448::FiniteField448Point;
+use crate::montgomery::MontgomeryCurve;
+
+/// u-coordinate of the Curve448 base point: u = 5 (little endian)
+pub const BASE_POINT: [u8; 56] = {
+    let mut u = [0; 56];
+    u[0] = 5;
+    u
+};
+
+/// Curve448: y^2 = x^3 + 156326x^2 + x
+pub fn curve448() -> MontgomeryCurve<FiniteField448Point> {
+    MontgomeryCurve::new(FiniteField448Point::from_u64(156326))
+}
+
+/// Clamp a 56 bytes scalar (RFC 7748 decodeScalar448):
+/// clear the 2 lowest bits (multiple of the cofactor 4) & set bit 447
+pub fn clamp_scalar(k: &[u8; 56]) -> [u8; 56] {
+    let mut k = *k;
+    k[0] &= 252;
+    k[55] |= 128;
+    k
+}
+
+/// X448(k, u): the u-coordinate of k * P (with u = u(P)), all little endian 56 bytes
+pub fn x448(k: &[u8; 56], u: &[u8; 56]) -> [u8; 56] {
+    let k = clamp_scalar(k);
+    let u = FiniteField448Point::from_bytes(u);
+
+    curve448().ladder(&k, 448, u).to_bytes()
+}
+
+/// Public key from a private key: X448(k, 5)
+pub fn public_key(k: &[u8; 56]) -> [u8; 56] {
+    x448(k, &BASE_POINT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_hex(s: &str) -> [u8; 56] {
+        let mut bytes = [0; 56];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).unwrap();
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_x448() {
+        // From: RFC 7748 section 5.2
+        let k = from_hex("3d262fddf9ec8e88495266fea19a34d28882acef045104d0d1aae121700a779c984c24f8cdd78fbff44943eba368f54b29259a4f1c600ad3");
+        let u = from_hex("06fce640fa3487bfda5f6cf2d5263f8aad88334cbd07437f020f08f9814dc031ddbdc38c19c6da2583fa5429db94ada18aa7a7fb4ef8a086");
+        assert_eq!(
+            x448(&k, &u),
+            from_hex("ce3e4ff95a60dc6697da1db1d85e6afbdf79b50a2412d7546d5f239fe14fbaadeb445fc66a01b0779d98223961111e21766282f73dd96b6f")
+        );
+
+        let k = from_hex("203d494428b8399352665ddca42f9de8fef600908e0d461cb021f8c538345dd77c3e4806e25f46d3315c44e0a5b4371282dd2c8d5be3095f");
+        let u = from_hex("0fbcc2f993cd56d3305b0b7d9e55d4c1a8fb5dbb52f8e9a1e9b6201b165d015894e56c4d3570bee52fe205e28a78b91cdfbde71ce8d157db");
+        assert_eq!(
+            x448(&k, &u),
+            from_hex("884a02576239ff7a2f2f63b2db6a9ff37047ac13568e1e30fe63c4a7ad1b3ee3a5700df34321d62077e63633c575c1c954514e99da7c179d")
+        );
+    }
+
+    /// k = u = 5 then repeat: k, u = X448(k, u), k
+    fn iterate(count: usize) -> [u8; 56] {
+        let mut k = BASE_POINT;
+        let mut u = BASE_POINT;
+        for _ in 0..count {
+            (k, u) = (x448(&k, &u), k);
+        }
+        k
+    }
+
+    #[test]
+    fn test_x448_iterations() {
+        // From: RFC 7748 section 5.2
+        assert_eq!(
+            iterate(1),
+            from_hex("3f482c8a9f19b01e6c46ee9711d9dc14fd4bf67af30765c2ae2b846a4d23a8cd0db897086239492caf350b51f833868b9bc2b3bca9cf4113")
+        );
+    }
+
+    #[test]
+    #[ignore = "slow without optimizations: run with cargo test --release -- --ignored"]
+    fn test_x448_iterations_1k() {
+        // From: RFC 7748 section 5.2
+        assert_eq!(
+            iterate(1_000),
+            from_hex("aa3b4749d55b9daf1e5b00288826c467274ce3ebbdd5c17b975e09d4af6c67cf10d087202db88286e2b79fceea3ec353ef54faa26e219f38")
+        );
+    }
+
+    #[test]
+    #[ignore = "slow: run with cargo test -- --ignored"]
+    fn test_x448_iterations_1m() {
+        // From: RFC 7748 section 5.2
+        assert_eq!(
+            iterate(1_000_000),
+            from_hex("077f453681caca3693198420bbe515cae0002472519b3e67661a7e89cab94695c8f4bcd66e61b9b9c946da8d524de3d69bd9d9d66b997e37")
+        );
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_key_exchange() {
+        // From: RFC 7748 section 6.2
+        let kA = from_hex("9a8f4925d1519f5775cf46b04b5800d4ee9ee8bae8bc5565d498c28dd9c9baf574a9419744897391006382a6f127ab1d9ac2d8c0a598726b");
+        let A = public_key(&kA);
+        assert_eq!(
+            A,
+            from_hex("9b08f7cc31b7e3e67d22d5aea121074a273bd2b83de09c63faa73d2c22c5d9bbc836647241d953d40c5b12da88120d53177f80e532c41fa0")
+        );
+
+        let kB = from_hex("1c306a7ac2a0e2e0990b294470cba339e6453772b075811d8fad0d1d6927c120bb5ee8972b0d3e21374c9c921b09d1b0366f10b65173992d");
+        let B = public_key(&kB);
+        assert_eq!(
+            B,
+            from_hex("3eb7a829b0cd20f5bcfc0b599b6feccf6da4627107bdb0d4f345b43027d8b972fc3e34fb4232a13ca706dcb57aec3dae07bdc1c67bf33609")
+        );
+
+        let kA_B = x448(&kA, &B);
+        let kB_A = x448(&kB, &A);
+        assert_eq!(kA_B, kB_A);
+        assert_eq!(
+            kA_B,
+            from_hex("07fff4181ac6cc95ec1c16a94a0f74d12da232ce40a77552281d282bb60c0b56fd2464c335543936521c24403085d59a449a5037514a879d")
+        );
+    }
+}